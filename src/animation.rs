@@ -0,0 +1,73 @@
+use egui::{Context, Id};
+use serde::{Deserialize, Serialize};
+
+/// 折叠/展开动画的缓动曲线
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Easing {
+    /// 线性
+    Linear,
+    /// ease-in-out-cubic
+    #[default]
+    CubicInOut,
+    /// ease-in-out-quint
+    QuintInOut,
+    /// ease-out-back，结尾略微回弹
+    BackOut,
+    /// 临界阻尼弹簧，无回弹
+    Spring,
+}
+
+impl Easing {
+    /// 将线性进度 `t`（0..=1）映射为缓动后的进度
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Self::QuintInOut => {
+                if t < 0.5 {
+                    16.0 * t.powi(5)
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(5) / 2.0
+                }
+            }
+            Self::BackOut => {
+                const C1: f32 = 1.70158;
+                const C3: f32 = C1 + 1.0;
+                1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
+            }
+            Self::Spring => {
+                // x(t) = 1 - (1 + ωt)·e^(-ωt)，归一化使 t = 1 时恰好到达终点
+                const OMEGA: f32 = 8.0;
+                let response = |t: f32| 1.0 - (1.0 + OMEGA * t) * (-OMEGA * t).exp();
+                response(t) / response(1.0)
+            }
+        }
+    }
+}
+
+/// 推进折叠/展开动画，返回线性进度（0 为折叠，1 为展开）
+///
+/// `duration` 不大于 0 时直接跳到目标值。
+pub(crate) fn animate_progress(ctx: &Context, id: Id, expanded: bool, duration: f32) -> f32 {
+    animate_to(ctx, id, if expanded { 1.0 } else { 0.0 }, duration)
+}
+
+/// 以线性插值将 `id` 对应的值推进到 `target`
+///
+/// `duration` 不大于 0 时直接跳到目标值。
+pub(crate) fn animate_to(ctx: &Context, id: Id, target: f32, duration: f32) -> f32 {
+    if duration <= 0.0 {
+        // 仍然写入动画内存，之后恢复动画时从当前位置开始
+        ctx.animate_value_with_time(id, target, 0.0);
+        target
+    } else {
+        ctx.animate_value_with_time(id, target, duration)
+    }
+}
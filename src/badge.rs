@@ -0,0 +1,113 @@
+use egui::{Color32, FontId, Painter, Pos2, Rect, Stroke, Vec2, Visuals};
+
+/// 徽标内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeContent {
+    /// 数字计数，为 0 时不显示
+    Count(u32),
+    /// 圆点，用于“有新内容”之类的提示
+    Dot,
+}
+
+/// 徽标严重程度，决定徽标颜色
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BadgeSeverity {
+    /// 普通提示
+    #[default]
+    Info,
+    /// 警告
+    Warning,
+    /// 错误
+    Error,
+}
+
+impl BadgeSeverity {
+    /// 当前主题下的徽标颜色
+    pub fn color(self, visuals: &Visuals) -> Color32 {
+        match self {
+            Self::Info => visuals.hyperlink_color,
+            Self::Warning => visuals.warn_fg_color,
+            Self::Error => visuals.error_fg_color,
+        }
+    }
+}
+
+/// 折叠栏按钮右上角的徽标
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Badge {
+    /// 内容
+    pub content: BadgeContent,
+    /// 严重程度
+    pub severity: BadgeSeverity,
+}
+
+impl Badge {
+    /// 数字徽标
+    pub fn count(count: u32) -> Self {
+        Self {
+            content: BadgeContent::Count(count),
+            severity: BadgeSeverity::default(),
+        }
+    }
+
+    /// 圆点徽标
+    pub fn dot() -> Self {
+        Self {
+            content: BadgeContent::Dot,
+            severity: BadgeSeverity::default(),
+        }
+    }
+
+    /// 设置严重程度
+    pub fn with_severity(mut self, severity: BadgeSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// 是否需要显示（计数为 0 时隐藏）
+    pub fn is_visible(&self) -> bool {
+        self.content != BadgeContent::Count(0)
+    }
+
+    /// 徽标上显示的文本，圆点没有文本；超过 99 时显示 `99+`
+    pub fn label(&self) -> Option<String> {
+        match self.content {
+            BadgeContent::Count(count) if count > 99 => Some("99+".to_owned()),
+            BadgeContent::Count(count) => Some(count.to_string()),
+            BadgeContent::Dot => None,
+        }
+    }
+}
+
+/// 在按钮区域右上角绘制徽标
+pub(crate) fn paint_badge(painter: &Painter, button_rect: Rect, badge: &Badge, visuals: &Visuals) {
+    if !badge.is_visible() {
+        return;
+    }
+
+    let fill = badge.severity.color(visuals);
+    // 用折叠栏背景色描边，使徽标与图标分开
+    let ring = Stroke::new(1.0, visuals.extreme_bg_color);
+    let corner = button_rect.right_top();
+
+    let Some(label) = badge.label() else {
+        let center = corner + Vec2::new(-3.0, 3.0);
+        painter.circle(center, 3.0, fill, ring);
+        return;
+    };
+
+    let text_color = if fill.intensity() > 0.5 {
+        Color32::BLACK
+    } else {
+        Color32::WHITE
+    };
+    let galley = painter.layout_no_wrap(label, FontId::proportional(9.0), text_color);
+    let height = 12.0;
+    let width = (galley.size().x + 6.0).max(height);
+    let rect = Rect::from_min_size(
+        Pos2::new(corner.x - width + 4.0, corner.y - 2.0),
+        Vec2::new(width, height),
+    );
+    painter.rect(rect, height / 2.0, fill, ring, egui::StrokeKind::Outside);
+    painter.galley(rect.center() - galley.size() / 2.0, galley, text_color);
+}
@@ -1,17 +1,14 @@
 use egui::{Context, Frame, Id, Response, Ui};
 use serde::{Deserialize, Serialize};
 
-/// 面板方向枚举
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum PanelSide {
-    Left,
-    Right,
-    Top,
-    Bottom,
-}
-
-/// 标签页特征，定义标签页的基本行为
-pub trait TabViewer {
+use crate::animation::{animate_progress, Easing};
+use crate::dock_collapsible::PanelSide;
+use crate::strings::{StringKey, Strings};
+
+/// 工具栏标签页特征，定义标签页的基本行为
+///
+/// 与 `egui_dock::TabViewer` 不同，工具栏自行管理标签页，不依赖 `DockState`。
+pub trait ToolbarTabViewer {
     type Tab: Clone + PartialEq + Serialize + for<'de> Deserialize<'de>;
 
     /// 获取标签页的标题
@@ -24,6 +21,11 @@ pub trait TabViewer {
     fn closable(&self, _tab: &Self::Tab) -> bool {
         false
     }
+
+    /// 用户关闭标签页前调用，返回 `false` 时取消关闭
+    fn on_close(&mut self, _tab: &Self::Tab) -> bool {
+        true
+    }
 }
 
 /// 工具栏状态信息
@@ -37,6 +39,51 @@ pub struct ToolbarState<Tab> {
     pub is_expanded: bool,
 }
 
+impl<Tab> ToolbarState<Tab> {
+    /// 在末尾添加标签页
+    pub fn add_tab(&mut self, tab: Tab) {
+        self.tabs.push(tab);
+    }
+
+    /// 在指定位置插入标签页，位置超出范围时添加到末尾
+    pub fn insert_tab(&mut self, index: usize, tab: Tab) {
+        let index = index.min(self.tabs.len());
+        self.tabs.insert(index, tab);
+        if let Some(selected) = self.selected_tab.as_mut() {
+            if *selected >= index {
+                *selected += 1;
+            }
+        }
+    }
+
+    /// 移除指定位置的标签页并修正选中的标签页
+    ///
+    /// 移除选中的标签页时改为选中相邻的标签页；没有剩余标签页时收叠工具栏。
+    pub fn remove_tab(&mut self, index: usize) -> Option<Tab> {
+        if index >= self.tabs.len() {
+            return None;
+        }
+        let tab = self.tabs.remove(index);
+
+        self.selected_tab = match self.selected_tab {
+            Some(selected) if selected > index => Some(selected - 1),
+            Some(selected) if selected == index => {
+                if self.tabs.is_empty() {
+                    None
+                } else {
+                    Some(index.min(self.tabs.len() - 1))
+                }
+            }
+            other => other,
+        };
+        if self.tabs.is_empty() {
+            self.is_expanded = false;
+        }
+
+        Some(tab)
+    }
+}
+
 impl<Tab> Default for ToolbarState<Tab> {
     fn default() -> Self {
         Self {
@@ -48,6 +95,8 @@ impl<Tab> Default for ToolbarState<Tab> {
 }
 
 /// 可折叠工具栏组件
+///
+/// 不依赖 egui_dock 的轻量替代方案，标签页由 [`ToolbarState`] 管理。
 pub struct CollapsibleToolbar<Tab> {
     /// 面板方向
     side: PanelSide,
@@ -63,6 +112,12 @@ pub struct CollapsibleToolbar<Tab> {
     min_size: f32,
     /// 是否可调整大小
     resizable: bool,
+    /// 动画持续时间（秒），为 0 时不播放动画
+    animation_duration: f32,
+    /// 动画缓动曲线
+    easing: Easing,
+    /// 内置界面文本
+    strings: Strings,
 }
 
 impl<Tab> CollapsibleToolbar<Tab>
@@ -79,6 +134,9 @@ where
             tabs_frame: None,
             min_size: 200.0,
             resizable: true,
+            animation_duration: 0.2,
+            easing: Easing::default(),
+            strings: Strings::default(),
         }
     }
 
@@ -112,24 +170,37 @@ where
         self
     }
 
+    /// 设置展开/收叠动画时长（秒），为 0 时不播放动画
+    pub fn animation_duration(mut self, duration: f32) -> Self {
+        self.animation_duration = duration.max(0.0);
+        self
+    }
+
+    /// 设置展开/收叠动画的缓动曲线
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// 设置内置界面文本（右键菜单和提示），默认为中文
+    pub fn strings(mut self, strings: Strings) -> Self {
+        self.strings = strings;
+        self
+    }
+
     /// 显示工具栏
     pub fn show(
         &self,
         ctx: &Context,
         id: impl Into<Id>,
-        viewer: &mut impl TabViewer<Tab = Tab>,
+        viewer: &mut impl ToolbarTabViewer<Tab = Tab>,
     ) -> Option<Response> {
         let id = id.into();
         let state_id = id.with("toolbar_state");
 
-        // 加载状态
+        // 加载状态（首次显示时使用默认标签页，之后允许关闭所有标签页）
         let mut state = self.load_state(ctx, state_id);
 
-        // 如果状态为空，使用默认标签页
-        if state.tabs.is_empty() {
-            state.tabs = self.default_tabs.clone();
-        }
-
         // 渲染工具栏
         let response = self.show_toolbar(ctx, id, &mut state, viewer);
 
@@ -139,6 +210,40 @@ where
         response
     }
 
+    /// 获取工具栏状态
+    pub fn state(&self, ctx: &Context, id: impl Into<Id>) -> ToolbarState<Tab> {
+        self.load_state(ctx, id.into().with("toolbar_state"))
+    }
+
+    /// 修改并保存工具栏状态
+    pub fn modify_state<R>(
+        &self,
+        ctx: &Context,
+        id: impl Into<Id>,
+        modify: impl FnOnce(&mut ToolbarState<Tab>) -> R,
+    ) -> R {
+        let state_id = id.into().with("toolbar_state");
+        let mut state = self.load_state(ctx, state_id);
+        let result = modify(&mut state);
+        self.save_state(ctx, state_id, &state);
+        result
+    }
+
+    /// 在末尾添加标签页
+    pub fn add_tab(&self, ctx: &Context, id: impl Into<Id>, tab: Tab) {
+        self.modify_state(ctx, id, |state| state.add_tab(tab));
+    }
+
+    /// 在指定位置插入标签页
+    pub fn insert_tab(&self, ctx: &Context, id: impl Into<Id>, index: usize, tab: Tab) {
+        self.modify_state(ctx, id, |state| state.insert_tab(index, tab));
+    }
+
+    /// 移除指定位置的标签页
+    pub fn remove_tab(&self, ctx: &Context, id: impl Into<Id>, index: usize) -> Option<Tab> {
+        self.modify_state(ctx, id, |state| state.remove_tab(index))
+    }
+
     /// 加载工具栏状态
     fn load_state(&self, ctx: &Context, state_id: Id) -> ToolbarState<Tab> {
         ctx.memory_mut(|mem| {
@@ -177,89 +282,86 @@ where
         ctx: &Context,
         id: Id,
         state: &mut ToolbarState<Tab>,
-        viewer: &mut impl TabViewer<Tab = Tab>,
+        viewer: &mut impl ToolbarTabViewer<Tab = Tab>,
     ) -> Option<Response> {
-        let animation_time = 0.2; // 动画持续时间（秒）
+        let progress = animate_progress(
+            ctx,
+            id.with("animation"),
+            state.is_expanded,
+            self.animation_duration,
+        );
+        let eased = self.easing.apply(progress);
+        // 动画结束且处于展开状态时才允许用户调整大小
+        let settled_expanded = state.is_expanded && progress >= 1.0;
+
+        // 展开和收叠使用不同的面板 ID，保留用户调整后的展开尺寸
+        let expanded_id = id.with("expanded");
+        let panel_id = if settled_expanded {
+            expanded_id
+        } else {
+            id.with("collapsed")
+        };
+        let remembered_size = egui::containers::panel::PanelState::load(ctx, expanded_id)
+            .map(|panel_state| panel_state.size());
 
-        // 根据面板方向创建相应的面板
-        match self.side {
-            PanelSide::Left => {
-                let collapsed_width = 16.0;  // VSCode style narrow sidebar
-                let expanded_width = self.min_size;
-
-                egui::SidePanel::left(id)
-                    .frame(self.expanded_frame.unwrap_or_else(|| {
-                        let mut frame = Frame::side_top_panel(&egui::Style::default());
-                        // Remove all padding for VSCode-style collapsed width
-                        if !state.is_expanded {
-                            frame.inner_margin = egui::Margin::ZERO;
-                            frame.outer_margin = egui::Margin::ZERO;
-                        }
-                        frame
-                    }))
-                    .min_width(collapsed_width)
-                    .default_width(if state.is_expanded { expanded_width } else { collapsed_width })
-                    .width_range(collapsed_width..=expanded_width * 2.0)
-                    .resizable(self.resizable && state.is_expanded)
-                    .show_animated(ctx, state.is_expanded, |ui| {
-                        self.show_content(ui, state, viewer)
-                    })
-                    .map(|r| r.response)
-            }
-            PanelSide::Right => {
-                let collapsed_width = 16.0;  // VSCode style narrow sidebar
-                let expanded_width = self.min_size;
-
-                egui::SidePanel::right(id)
-                    .frame(self.expanded_frame.unwrap_or_else(|| {
-                        let mut frame = Frame::side_top_panel(&egui::Style::default());
-                        // Remove all padding for VSCode-style collapsed width
-                        if !state.is_expanded {
-                            frame.inner_margin = egui::Margin::ZERO;
-                            frame.outer_margin = egui::Margin::ZERO;
-                        }
-                        frame
-                    }))
-                    .min_width(collapsed_width)
-                    .default_width(if state.is_expanded { expanded_width } else { collapsed_width })
-                    .width_range(collapsed_width..=expanded_width * 2.0)
-                    .resizable(self.resizable && state.is_expanded)
-                    .show_animated(ctx, state.is_expanded, |ui| {
-                        self.show_content(ui, state, viewer)
-                    })
-                    .map(|r| r.response)
+        let is_vertical = matches!(self.side, PanelSide::Left | PanelSide::Right);
+        let frame = self.expanded_frame.unwrap_or_else(|| {
+            let mut frame = Frame::side_top_panel(&egui::Style::default());
+            // Remove all padding for VSCode-style collapsed width
+            if is_vertical && !state.is_expanded {
+                frame.inner_margin = egui::Margin::ZERO;
+                frame.outer_margin = egui::Margin::ZERO;
             }
-            PanelSide::Top => {
-                let collapsed_height = 35.0;
-                let expanded_height = self.min_size;
-
-                egui::TopBottomPanel::top(id)
-                    .frame(self.expanded_frame.unwrap_or_else(|| Frame::side_top_panel(&egui::Style::default())))
-                    .min_height(collapsed_height)
-                    .default_height(if state.is_expanded { expanded_height } else { collapsed_height })
-                    .height_range(collapsed_height..=expanded_height * 2.0)
-                    .resizable(self.resizable && state.is_expanded)
-                    .show_animated(ctx, state.is_expanded, |ui| {
-                        self.show_content(ui, state, viewer)
-                    })
-                    .map(|r| r.response)
+            frame
+        });
+
+        // 根据面板方向创建相应的面板
+        let response = match self.side {
+            PanelSide::Left | PanelSide::Right => {
+                let collapsed_width = 16.0; // VSCode style narrow sidebar
+                let expanded_width = remembered_size.map_or(self.min_size, |size| size.x);
+                let panel = if self.side == PanelSide::Left {
+                    egui::SidePanel::left(panel_id)
+                } else {
+                    egui::SidePanel::right(panel_id)
+                };
+                let panel = panel
+                    .frame(frame)
+                    .resizable(self.resizable && settled_expanded);
+                let panel = if settled_expanded {
+                    panel
+                        .default_width(expanded_width)
+                        .width_range(collapsed_width..=self.min_size * 2.0)
+                } else {
+                    panel.exact_width(egui::lerp(collapsed_width..=expanded_width, eased))
+                };
+
+                panel.show(ctx, |ui| self.show_content(ui, state, viewer))
             }
-            PanelSide::Bottom => {
+            PanelSide::Top | PanelSide::Bottom => {
                 let collapsed_height = 35.0;
-                let expanded_height = self.min_size;
-
-                egui::TopBottomPanel::bottom(id)
-                    .frame(self.expanded_frame.unwrap_or_else(|| Frame::side_top_panel(&egui::Style::default())))
-                    .min_height(collapsed_height)
-                    .default_height(if state.is_expanded { expanded_height } else { collapsed_height })
-                    .height_range(collapsed_height..=expanded_height * 2.0)
-                    .resizable(self.resizable && state.is_expanded)
-                    .show_animated(ctx, state.is_expanded, |ui| {
-                        self.show_content(ui, state, viewer)
-                    })
-                    .map(|r| r.response)
+                let expanded_height = remembered_size.map_or(self.min_size, |size| size.y);
+                let panel = if self.side == PanelSide::Top {
+                    egui::TopBottomPanel::top(panel_id)
+                } else {
+                    egui::TopBottomPanel::bottom(panel_id)
+                };
+                let panel = panel
+                    .frame(frame)
+                    .resizable(self.resizable && settled_expanded);
+                let panel = if settled_expanded {
+                    panel
+                        .default_height(expanded_height)
+                        .height_range(collapsed_height..=self.min_size * 2.0)
+                } else {
+                    panel.exact_height(egui::lerp(collapsed_height..=expanded_height, eased))
+                };
+
+                panel.show(ctx, |ui| self.show_content(ui, state, viewer))
             }
-        }
+        };
+
+        Some(response.response)
     }
 
     /// 显示工具栏内容
@@ -267,7 +369,7 @@ where
         &self,
         ui: &mut Ui,
         state: &mut ToolbarState<Tab>,
-        viewer: &mut impl TabViewer<Tab = Tab>,
+        viewer: &mut impl ToolbarTabViewer<Tab = Tab>,
     ) {
         if state.is_expanded {
             // 展开状态：显示标签页和内容
@@ -283,7 +385,7 @@ where
         &self,
         ui: &mut Ui,
         state: &mut ToolbarState<Tab>,
-        viewer: &mut impl TabViewer<Tab = Tab>,
+        viewer: &mut impl ToolbarTabViewer<Tab = Tab>,
     ) {
         // 标签页栏
         let _tabs_response = self.show_tab_bar(ui, state, viewer);
@@ -306,7 +408,7 @@ where
         &self,
         ui: &mut Ui,
         state: &mut ToolbarState<Tab>,
-        viewer: &mut impl TabViewer<Tab = Tab>,
+        viewer: &mut impl ToolbarTabViewer<Tab = Tab>,
     ) {
         let is_vertical = matches!(self.side, PanelSide::Left | PanelSide::Right);
 
@@ -315,15 +417,8 @@ where
             ui.vertical(|ui| {
                 ui.spacing_mut().item_spacing.y = 2.0;
                 for (idx, tab) in state.tabs.iter().enumerate() {
-                    let is_selected = state.selected_tab == Some(idx);
-
-                    // 提取标签页标题的第一个字符或图标
                     let title = viewer.title(tab);
-                    let short_title = if let Some(icon_end) = title.find(' ') {
-                        &title[..icon_end] // 只显示图标部分
-                    } else {
-                        &title[..title.len().min(2)] // 或者前两个字符
-                    };
+                    let short_title = short_title(&title);
 
                     let button = egui::Button::new(short_title)
                         // VSCode style: no selection state when collapsed
@@ -340,15 +435,8 @@ where
             ui.horizontal(|ui| {
                 ui.spacing_mut().item_spacing.x = 2.0;
                 for (idx, tab) in state.tabs.iter().enumerate() {
-                    let is_selected = state.selected_tab == Some(idx);
-
-                    // 提取标签页标题的第一个字符或图标
                     let title = viewer.title(tab);
-                    let short_title = if let Some(icon_end) = title.find(' ') {
-                        &title[..icon_end] // 只显示图标部分
-                    } else {
-                        &title[..title.len().min(2)] // 或者前两个字符
-                    };
+                    let short_title = short_title(&title);
 
                     let button = egui::Button::new(short_title)
                         // VSCode style: no selection state when collapsed
@@ -368,58 +456,77 @@ where
         &self,
         ui: &mut Ui,
         state: &mut ToolbarState<Tab>,
-        viewer: &mut impl TabViewer<Tab = Tab>,
+        viewer: &mut impl ToolbarTabViewer<Tab = Tab>,
     ) -> Response {
-        ui.horizontal(|ui| {
-            ui.spacing_mut().item_spacing.x = 1.0;
+        let mut close_request = None;
 
-            // 标签页按钮
-            for (idx, tab) in state.tabs.iter().enumerate() {
-                let is_selected = state.selected_tab == Some(idx);
+        let response = ui
+            .horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 1.0;
 
-                // 创建带有样式的标签页按钮
-                let button = egui::Button::new(viewer.title(tab))
-                    .selected(is_selected)
-                    .corner_radius(4.0);
+                // 标签页按钮
+                for (idx, tab) in state.tabs.iter().enumerate() {
+                    let is_selected = state.selected_tab == Some(idx);
 
-                let response = ui.add(button);
+                    // 创建带有样式的标签页按钮
+                    let button = egui::Button::new(viewer.title(tab))
+                        .selected(is_selected)
+                        .corner_radius(4.0);
+
+                    let response = ui.add(button);
+
+                    if response.clicked() {
+                        if is_selected {
+                            // 点击当前选中的标签页，收叠工具栏
+                            state.selected_tab = None;
+                            state.is_expanded = false;
+                        } else {
+                            // 选中新的标签页
+                            state.selected_tab = Some(idx);
+                            state.is_expanded = true;
+                        }
+                    }
 
-                if response.clicked() {
-                    if is_selected {
-                        // 点击当前选中的标签页，收叠工具栏
-                        state.selected_tab = None;
-                        state.is_expanded = false;
-                    } else {
-                        // 选中新的标签页
-                        state.selected_tab = Some(idx);
-                        state.is_expanded = true;
+                    // 右键菜单（如果标签页可关闭）
+                    if viewer.closable(tab) {
+                        response.context_menu(|ui| {
+                            if ui.button(self.strings.get(StringKey::CloseTab)).clicked() {
+                                close_request = Some(idx);
+                                ui.close();
+                            }
+                        });
                     }
                 }
 
-                // 右键菜单（如果标签页可关闭）
-                if viewer.closable(tab) {
-                    response.context_menu(|ui| {
-                        if ui.button("关闭标签页").clicked() {
-                            // TODO: 实现关闭标签页的逻辑
-                            ui.close();
-                        }
-                    });
-                }
+                // 添加弹性空间
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    // 收叠按钮
+                    let close_button = egui::Button::new("✕").small().corner_radius(2.0);
+
+                    let tooltip = self.strings.get(StringKey::CollapseToolbar);
+                    if ui.add(close_button).on_hover_text(tooltip).clicked() {
+                        state.is_expanded = false;
+                        state.selected_tab = None;
+                    }
+                });
+            })
+            .response;
+
+        // 遍历结束后再关闭，由查看器决定是否允许
+        if let Some(idx) = close_request {
+            if viewer.on_close(&state.tabs[idx]) {
+                state.remove_tab(idx);
             }
+        }
 
-            // 添加弹性空间
-            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                // 收叠按钮
-                let close_button = egui::Button::new("✕")
-                    .small()
-                    .corner_radius(2.0);
+        response
+    }
+}
 
-                if ui.add(close_button).on_hover_text("收叠工具栏").clicked() {
-                    state.is_expanded = false;
-                    state.selected_tab = None;
-                }
-            });
-        })
-        .response
+/// 收叠状态下显示的短标题：空格前的图标部分，没有空格时取前两个字符
+fn short_title(title: &str) -> String {
+    match title.split_once(' ') {
+        Some((icon, _)) => icon.to_owned(),
+        None => title.chars().take(2).collect(),
     }
 }
@@ -1,18 +1,16 @@
 use egui::{Context, Frame, Id, Response, Ui, Vec2};
-use egui_dock::{DockState, TabViewer};
+use egui_dock::{DockState, TabIndex, TabViewer};
 use egui_phosphor::regular as phosphor;
+
+use crate::animation::{animate_progress, animate_to, Easing};
+use crate::badge::{paint_badge, Badge};
+use crate::icon_registry::{paint_fallback, paint_glyph, IconRegistry};
+use crate::keymap::{find_conflicts, KeyAction, Keymap, KeymapConflict, Shortcut};
+use crate::strings::{StringKey, Strings};
+use crate::tab_drag::{DockTarget, PressedTab, TabButtonTracker, TabPath};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-/// 缓动函数：ease-in-out-cubic
-fn ease_in_out_cubic(t: f32) -> f32 {
-    if t < 0.5 {
-        4.0 * t * t * t
-    } else {
-        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
-    }
-}
-
 /// 面板方向枚举
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PanelSide {
@@ -35,6 +33,60 @@ pub struct PanelState {
     pub max_size: Option<f32>,
     /// 是否可调整大小
     pub resizable: bool,
+    /// 是否固定；未固定的面板在指针离开或失去焦点后自动折叠
+    #[serde(default = "default_pinned")]
+    pub pinned: bool,
+    /// 用户调整后的折叠栏按钮顺序（按钮 ID），为空时按添加顺序显示
+    #[serde(default)]
+    pub button_order: Vec<String>,
+    /// 通过右键菜单隐藏的折叠栏按钮（按钮 ID）
+    #[serde(default)]
+    pub hidden_buttons: Vec<String>,
+    /// 是否因为窗口太小被自动折叠；窗口变大后恢复展开
+    #[serde(default)]
+    pub auto_collapsed: bool,
+}
+
+/// 按面板 ID 保存的面板状态，同一方向可以有多个这样的面板
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyedPanelState {
+    /// 面板所在方向
+    pub side: PanelSide,
+    /// 折叠状态、尺寸和按钮
+    pub state: PanelState,
+}
+
+/// 面板状态在 [`CollapsibleDockState`] 中的键
+///
+/// 可以由 [`PanelSide`] 或面板 ID 转换，因此按方向操作状态的方法也可以用于设置了面板 ID 的面板。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PanelKey {
+    /// 未设置面板 ID 的面板，状态按方向保存在 `panels` 中
+    Side(PanelSide),
+    /// 设置了面板 ID 的面板，状态保存在 `keyed_panels` 中
+    Id(String),
+}
+
+impl From<PanelSide> for PanelKey {
+    fn from(side: PanelSide) -> Self {
+        Self::Side(side)
+    }
+}
+
+impl From<&str> for PanelKey {
+    fn from(id: &str) -> Self {
+        Self::Id(id.to_owned())
+    }
+}
+
+impl From<String> for PanelKey {
+    fn from(id: String) -> Self {
+        Self::Id(id)
+    }
+}
+
+fn default_pinned() -> bool {
+    true
 }
 
 impl Default for PanelState {
@@ -45,6 +97,55 @@ impl Default for PanelState {
             min_size: 150.0,
             max_size: None,
             resizable: true,
+            pinned: true,
+            button_order: Vec::new(),
+            hidden_buttons: Vec::new(),
+            auto_collapsed: false,
+        }
+    }
+}
+
+/// 折叠/展开动画过程中的内容过渡方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransitionMode {
+    /// 展开内容按当前面板尺寸布局，超出部分被裁剪
+    Clip,
+    /// 展开内容保持完整尺寸，随面板内侧边缘滑入/滑出
+    Slide,
+    /// 折叠栏与展开内容交叉淡入淡出
+    CrossFade,
+    /// 展开内容保持完整尺寸，随面板变大逐渐显露
+    #[default]
+    Reveal,
+}
+
+/// 折叠面板通过折叠栏展开时的方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExpandMode {
+    /// 展开面板，占用布局空间
+    #[default]
+    Docked,
+    /// 点击折叠栏按钮时，面板内容浮动显示在中央区域上方，不改变布局
+    Overlay,
+    /// 悬停或点击折叠栏按钮时浮动显示
+    OverlayOnHover,
+}
+
+/// 响应式折叠断点，窗口尺寸进入断点范围时自动折叠面板
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Breakpoint {
+    /// 窗口宽度小于该值时折叠
+    WindowWidthBelow(f32),
+    /// 窗口高度小于该值时折叠
+    WindowHeightBelow(f32),
+}
+
+impl Breakpoint {
+    /// 指定的窗口尺寸是否处于断点范围内
+    pub fn applies(self, window_size: Vec2) -> bool {
+        match self {
+            Self::WindowWidthBelow(width) => window_size.x < width,
+            Self::WindowHeightBelow(height) => window_size.y < height,
         }
     }
 }
@@ -54,10 +155,22 @@ impl Default for PanelState {
 pub struct CollapsibleDockState {
     /// 各个面板的状态
     pub panels: HashMap<PanelSide, PanelState>,
-    /// 动画持续时间（秒）
+    /// 动画持续时间（秒），为 0 时不播放动画
     pub animation_duration: f32,
+    /// 动画缓动曲线
+    #[serde(default)]
+    pub easing: Easing,
+    /// 动画过程中的内容过渡方式
+    #[serde(default)]
+    pub transition: TransitionMode,
     /// 是否启用状态持久化
     pub persist_state: bool,
+    /// 被移动到另一侧的面板：键为创建面板时的方向，值为当前方向
+    #[serde(default)]
+    pub side_overrides: HashMap<PanelSide, PanelSide>,
+    /// 设置了 [`CollapsibleDockPanel::with_panel_id`] 的面板，按面板 ID 保存
+    #[serde(default)]
+    pub keyed_panels: HashMap<String, KeyedPanelState>,
 }
 
 impl Default for CollapsibleDockState {
@@ -71,7 +184,11 @@ impl Default for CollapsibleDockState {
         Self {
             panels,
             animation_duration: 0.2,
+            easing: Easing::default(),
+            transition: TransitionMode::default(),
             persist_state: true,
+            side_overrides: HashMap::new(),
+            keyed_panels: HashMap::new(),
         }
     }
 }
@@ -82,53 +199,56 @@ impl CollapsibleDockState {
         Self::default()
     }
 
+    /// 获取面板状态
+    pub fn panel_state(&self, panel: impl Into<PanelKey>) -> Option<&PanelState> {
+        match panel.into() {
+            PanelKey::Side(side) => self.panels.get(&side),
+            PanelKey::Id(id) => self.keyed_panels.get(&id).map(|keyed| &keyed.state),
+        }
+    }
+
+    /// 获取可变的面板状态
+    pub fn panel_state_mut(&mut self, panel: impl Into<PanelKey>) -> Option<&mut PanelState> {
+        match panel.into() {
+            PanelKey::Side(side) => self.panels.get_mut(&side),
+            PanelKey::Id(id) => self.keyed_panels.get_mut(&id).map(|keyed| &mut keyed.state),
+        }
+    }
+
     /// 设置面板状态
-    pub fn set_panel_collapsed(&mut self, side: PanelSide, collapsed: bool) {
-        if let Some(panel) = self.panels.get_mut(&side) {
+    pub fn set_panel_collapsed(&mut self, panel: impl Into<PanelKey>, collapsed: bool) {
+        if let Some(panel) = self.panel_state_mut(panel) {
             panel.collapsed = collapsed;
         }
     }
 
     /// 获取面板是否折叠
-    pub fn is_panel_collapsed(&self, side: PanelSide) -> bool {
-        self.panels.get(&side).map(|p| p.collapsed).unwrap_or(false)
+    pub fn is_panel_collapsed(&self, panel: impl Into<PanelKey>) -> bool {
+        self.panel_state(panel)
+            .map(|p| p.collapsed)
+            .unwrap_or(false)
     }
 
     /// 切换面板折叠状态
-    pub fn toggle_panel(&mut self, side: PanelSide) {
-        if let Some(panel) = self.panels.get_mut(&side) {
+    pub fn toggle_panel(&mut self, panel: impl Into<PanelKey>) {
+        if let Some(panel) = self.panel_state_mut(panel) {
             panel.collapsed = !panel.collapsed;
         }
     }
 
-    /// 设置面板尺寸
-    pub fn set_panel_size(&mut self, side: PanelSide, size: f32) {
-        if let Some(panel) = self.panels.get_mut(&side) {
-            // 确保尺寸在合理范围内，但不强制使用min_size作为最小值
-            let validated_size = if size < 100.0 {
-                // 如果尺寸太小，使用一个合理的默认值
-                300.0
-            } else {
-                size
-            };
-
-            panel.size = validated_size;
+    /// 设置面板尺寸，限制在面板的最小尺寸和最大尺寸之间
+    pub fn set_panel_size(&mut self, panel: impl Into<PanelKey>, size: f32) {
+        if let Some(panel) = self.panel_state_mut(panel) {
+            panel.size = size.max(panel.min_size);
             if let Some(max_size) = panel.max_size {
                 panel.size = panel.size.min(max_size);
             }
-
-            // 调试信息
-            // println!("set_panel_size: side={:?}, old_size={}, new_size={}",
-            //     side, panel.size, validated_size);
-        } else {
-            // println!("set_panel_size: panel not found for side={:?}", side);
         }
     }
 
     /// 获取面板尺寸
-    pub fn get_panel_size(&self, side: PanelSide) -> f32 {
-        self.panels
-            .get(&side)
+    pub fn get_panel_size(&self, panel: impl Into<PanelKey>) -> f32 {
+        self.panel_state(panel)
             .map(|p| p.size)
             .unwrap_or(PanelState::default().size)
     }
@@ -143,6 +263,11 @@ impl CollapsibleDockState {
         }
     }
 
+    /// 从 egui 内存加载状态，不存在时返回 `None`
+    pub fn try_load_from_memory(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.memory_mut(|mem| mem.data.get_persisted::<Self>(id.with("dock_state")))
+    }
+
     /// 从 egui 内存加载状态
     pub fn load_from_memory(ctx: &Context, id: Id) -> Self {
         ctx.memory_mut(|mem| {
@@ -153,9 +278,36 @@ impl CollapsibleDockState {
     }
 }
 
+/// 面板事件，由 [`CollapsibleDockPanel::show`] 在状态变化时产生
+#[derive(Debug, Clone, PartialEq)]
+pub enum PanelEvent {
+    /// 面板被折叠
+    Collapsed,
+    /// 面板被展开
+    Expanded,
+    /// 用户调整了面板尺寸；拖动分隔线时在松开后发出一次
+    Resized { old: f32, new: f32 },
+    /// 折叠栏上的按钮被激活
+    ButtonActivated(usize),
+    /// 折叠/展开动画结束
+    AnimationFinished,
+    /// 按下的快捷键绑定了多个操作，未执行任何操作
+    ShortcutConflict(Shortcut),
+    /// 浮动面板被打开
+    OverlayOpened,
+    /// 浮动面板被关闭
+    OverlayClosed,
+    /// 用户拖动调整了折叠栏按钮的顺序
+    ButtonsReordered,
+    /// 面板被移动到另一侧
+    Moved { from: PanelSide, to: PanelSide },
+}
+
 /// 可折叠面板按钮配置
 #[derive(Debug, Clone)]
 pub struct CollapsibleButton {
+    /// 稳定 ID，用于持久化按钮顺序，默认为按钮文本
+    pub id: String,
     /// 按钮文本
     pub text: String,
     /// 按钮图标
@@ -164,18 +316,29 @@ pub struct CollapsibleButton {
     pub tooltip: Option<String>,
     /// 是否选中
     pub selected: bool,
+    /// 右上角的徽标
+    pub badge: Option<Badge>,
 }
 
 impl CollapsibleButton {
     pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
         Self {
-            text: text.into(),
+            id: text.clone(),
+            text,
             icon: None,
             tooltip: None,
             selected: false,
+            badge: None,
         }
     }
 
+    /// 设置稳定 ID，按钮文本可能变化（如本地化）时使用
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
     pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
         self.icon = Some(icon.into());
         self
@@ -190,12 +353,163 @@ impl CollapsibleButton {
         self.selected = selected;
         self
     }
+
+    /// 设置徽标
+    pub fn with_badge(mut self, badge: Badge) -> Self {
+        self.badge = Some(badge);
+        self
+    }
+
+    /// 图标注册表中的查找键：`icon`（保留 `svg:` 前缀），未设置时使用按钮文本
+    ///
+    /// `svg:Files` 和按钮文本 `Files` 是不同的键，分别对应内置绘制图标和字形图标。
+    pub fn icon_key(&self) -> &str {
+        self.icon.as_deref().unwrap_or(&self.text)
+    }
+}
+
+/// 所有面板方向
+const ALL_SIDES: [PanelSide; 4] = [
+    PanelSide::Left,
+    PanelSide::Right,
+    PanelSide::Top,
+    PanelSide::Bottom,
+];
+
+/// 移动面板时窗口边缘目标区域的厚度
+const MOVE_TARGET_THICKNESS: f32 = 64.0;
+
+/// 面板在指定方向上占用的窗口尺寸：左右面板为宽度，上下面板为高度
+fn side_extent(side: PanelSide, content_size: Vec2) -> f32 {
+    match side {
+        PanelSide::Left | PanelSide::Right => content_size.x,
+        PanelSide::Top | PanelSide::Bottom => content_size.y,
+    }
+}
+
+/// 窗口边缘的移动目标区域
+fn move_target_rect(content_rect: egui::Rect, side: PanelSide) -> egui::Rect {
+    let thickness = MOVE_TARGET_THICKNESS;
+    let (min, max) = (content_rect.min, content_rect.max);
+    match side {
+        PanelSide::Left => egui::Rect::from_min_max(min, egui::pos2(min.x + thickness, max.y)),
+        PanelSide::Right => egui::Rect::from_min_max(egui::pos2(max.x - thickness, min.y), max),
+        PanelSide::Top => egui::Rect::from_min_max(min, egui::pos2(max.x, min.y + thickness)),
+        PanelSide::Bottom => egui::Rect::from_min_max(egui::pos2(min.x, max.y - thickness), max),
+    }
+}
+
+/// 指针所在的移动目标方向，位于角落时取最近的边
+fn move_target_at(content_rect: egui::Rect, pos: egui::Pos2) -> Option<PanelSide> {
+    let distance = |side| match side {
+        PanelSide::Left => pos.x - content_rect.left(),
+        PanelSide::Right => content_rect.right() - pos.x,
+        PanelSide::Top => pos.y - content_rect.top(),
+        PanelSide::Bottom => content_rect.bottom() - pos.y,
+    };
+    ALL_SIDES
+        .into_iter()
+        .filter(|side| move_target_rect(content_rect, *side).contains(pos))
+        .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+}
+
+/// 折叠栏右键菜单中的自定义菜单项
+///
+/// 参数为被右键点击的按钮 ID，在折叠栏空白处点击时为 `None`。
+pub type StripMenuCallback = Box<dyn FnMut(&mut Ui, Option<&str>)>;
+
+/// 折叠栏右键菜单选择的操作，在面板渲染结束后执行
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StripAction {
+    /// 隐藏按钮
+    HideButton(usize),
+    /// 重新显示隐藏的按钮
+    ShowButton(usize),
+    /// 显示所有隐藏的按钮
+    ShowAllButtons,
+    /// 移动面板到另一侧
+    MoveTo(PanelSide),
+    /// 恢复默认尺寸
+    ResetSize,
+    /// 切换折叠状态
+    Toggle,
+}
+
+/// 拖动排序时其余按钮让出位置的动画时长（秒）
+const BUTTON_REORDER_DURATION: f32 = 0.15;
+
+/// 双击分隔线循环切换尺寸的进度
+#[derive(Debug, Clone, Copy)]
+struct SizeCycle {
+    /// 开始循环前用户调整的尺寸，折叠时恢复
+    saved: f32,
+    /// 当前所在的步骤（默认尺寸、最大化）
+    step: usize,
+    /// 当前步骤设置的尺寸，尺寸被用户改变后重新开始循环
+    size: f32,
+}
+
+/// 拖动调整尺寸时，小于 `min_size` 的这个比例就折叠面板
+const COLLAPSE_SNAP_RATIO: f32 = 0.5;
+
+/// 折叠栏的厚度：图标尺寸（14px）加两侧各 6px 边距
+const COLLAPSED_SIZE: f32 = 14.0 + 6.0 * 2.0;
+
+/// 指针到面板靠窗口一侧边缘的距离，即拖动到该位置时面板的尺寸
+fn edge_distance(side: PanelSide, rect: egui::Rect, pos: egui::Pos2) -> f32 {
+    match side {
+        PanelSide::Left => pos.x - rect.left(),
+        PanelSide::Right => rect.right() - pos.x,
+        PanelSide::Top => pos.y - rect.top(),
+        PanelSide::Bottom => rect.bottom() - pos.y,
+    }
+}
+
+/// 面板从靠窗口一侧的边缘延伸 `size` 后的区域
+fn rect_with_extent(side: PanelSide, rect: egui::Rect, size: f32) -> egui::Rect {
+    let mut rect = rect;
+    match side {
+        PanelSide::Left => rect.max.x = rect.min.x + size,
+        PanelSide::Right => rect.min.x = rect.max.x - size,
+        PanelSide::Top => rect.max.y = rect.min.y + size,
+        PanelSide::Bottom => rect.min.y = rect.max.y - size,
+    }
+    rect
+}
+
+/// 按钮绑定的标签页匹配条件
+pub type TabPredicate<T> = Box<dyn Fn(&T) -> bool>;
+
+/// 折叠栏按钮拖动排序的状态
+#[derive(Debug, Clone, Copy)]
+struct ButtonDrag {
+    /// 被拖动按钮开始拖动时的显示位置
+    slot: usize,
+    /// 沿折叠栏方向的累计拖动距离
+    offset: f32,
+}
+
+impl ButtonDrag {
+    /// 被拖动按钮当前所在的位置（以按钮间距为单位，可以是小数）
+    fn visual_slot(self, pitch: f32, count: usize) -> f32 {
+        let last = count.saturating_sub(1) as f32;
+        (self.slot as f32 + self.offset / pitch).clamp(0.0, last)
+    }
+
+    /// 松开时按钮将放置到的位置
+    fn target_slot(self, pitch: f32, count: usize) -> usize {
+        self.visual_slot(pitch, count).round() as usize
+    }
 }
 
 /// 可折叠 Dock 面板
 pub struct CollapsibleDockPanel<Tab: TabViewer> {
     /// 面板方向
     side: PanelSide,
+    /// 创建面板时的方向，用于持久化移动后的方向
+    home_side: PanelSide,
+    /// 面板 ID，设置后状态按 ID 而不是方向保存
+    panel_id: Option<String>,
     /// Dock 状态
     dock_state: DockState<Tab::Tab>,
     /// 折叠状态
@@ -206,12 +520,74 @@ pub struct CollapsibleDockPanel<Tab: TabViewer> {
     frame: Option<Frame>,
     /// 按钮列表（折叠时显示）
     buttons: Vec<CollapsibleButton>,
+    /// 按钮图标注册表
+    icons: IconRegistry,
+    /// 内置界面文本
+    strings: Strings,
+    /// 每个按钮绑定的标签页（与 `buttons` 一一对应）
+    button_bindings: Vec<Option<TabPredicate<Tab::Tab>>>,
+    /// 上一次 Dock 焦点标签页对应的按钮
+    last_focused_button: Option<usize>,
     /// 前一帧的折叠状态（用于检测状态变化）
     previous_collapsed: bool,
     /// 状态是否已加载
     state_loaded: bool,
     /// 当前活动的按钮索引
     active_button_index: Option<usize>,
+    /// 上一帧是否处于动画中
+    was_animating: bool,
+    /// 尺寸是否正在以动画方式过渡到新设置的值
+    size_transition: bool,
+    /// 快捷键绑定表
+    keymap: Keymap,
+    /// 通过折叠栏展开的方式
+    expand_mode: ExpandMode,
+    /// 浮动面板是否打开
+    overlay_open: bool,
+    /// 前一帧浮动面板是否打开（用于检测状态变化）
+    previous_overlay_open: bool,
+    /// 未固定面板自动折叠前的延迟（秒）
+    auto_collapse_delay: f32,
+    /// 未固定面板的指针离开时间
+    pointer_left_at: Option<f64>,
+    /// 正在拖动排序的折叠栏按钮
+    button_drag: Option<ButtonDrag>,
+    /// 默认尺寸，用于“重置尺寸”
+    default_size: f32,
+    /// 折叠栏右键菜单中的自定义菜单项
+    strip_menu: Option<StripMenuCallback>,
+    /// 本帧在右键菜单中选择的操作
+    strip_action: Option<StripAction>,
+    /// 是否正在拖动面板以移动到另一侧
+    moving_panel: bool,
+    /// 拖动面板时指针所在的目标方向
+    move_target: Option<PanelSide>,
+    /// 面板刚移动到新方向，需要从折叠状态播放展开动画
+    side_transition: bool,
+    /// 上一帧的窗口内容区域尺寸，用于在宽度和高度之间换算面板尺寸
+    content_size: Option<Vec2>,
+    /// 响应式折叠断点
+    breakpoints: Vec<Breakpoint>,
+    /// 响应式折叠优先级，空间不足时优先级低的面板先折叠
+    collapse_priority: i32,
+    /// 工作区是否因为中央区域空间不足要求折叠本面板
+    space_collapse: bool,
+    /// 上一帧是否需要响应式折叠
+    responsive_collapse: bool,
+    /// 开始拖动调整尺寸前的尺寸，拖动到折叠阈值以下时恢复
+    size_before_resize: Option<f32>,
+    /// 最大化时为中央区域保留的最小尺寸，为 `None` 时双击分隔线不经过最大化
+    maximize_min_central: Option<f32>,
+    /// 双击分隔线循环切换尺寸的进度
+    size_cycle: Option<SizeCycle>,
+    /// 上一帧显示面板前剩余的空间（面板方向上的尺寸），用于计算最大化尺寸
+    available_extent: Option<f32>,
+    /// 本帧产生的事件
+    events: Vec<PanelEvent>,
+    /// 可能在 `show` 之外产生的事件（如 `set_side`、`cycle_size`），由下一次 `show` 报告
+    pending_events: Vec<PanelEvent>,
+    /// 在标签栏中按下的标签页，用于跨面板拖放
+    pressed_tab: PressedTab,
 }
 
 impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
@@ -219,15 +595,72 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
     pub fn new(side: PanelSide, state_id: Id) -> Self {
         Self {
             side,
+            home_side: side,
+            panel_id: None,
             dock_state: DockState::new(vec![]),
             collapsible_state: CollapsibleDockState::new(),
             state_id,
             frame: None,
             buttons: Vec::new(),
+            icons: IconRegistry::with_defaults(),
+            strings: Strings::default(),
+            button_bindings: Vec::new(),
+            last_focused_button: None,
             previous_collapsed: false,
             state_loaded: false,
             active_button_index: Some(0), // 默认第一个按钮为活动状态
+            was_animating: false,
+            size_transition: false,
+            keymap: Keymap::new(),
+            expand_mode: ExpandMode::default(),
+            overlay_open: false,
+            previous_overlay_open: false,
+            auto_collapse_delay: 0.5,
+            pointer_left_at: None,
+            button_drag: None,
+            default_size: PanelState::default().size,
+            strip_menu: None,
+            strip_action: None,
+            moving_panel: false,
+            move_target: None,
+            side_transition: false,
+            content_size: None,
+            breakpoints: Vec::new(),
+            collapse_priority: 0,
+            space_collapse: false,
+            responsive_collapse: false,
+            size_before_resize: None,
+            maximize_min_central: None,
+            size_cycle: None,
+            available_extent: None,
+            events: Vec::new(),
+            pending_events: Vec::new(),
+            pressed_tab: PressedTab::default(),
+        }
+    }
+
+    /// 设置面板 ID
+    ///
+    /// 设置 ID 的面板的状态只保存在 [`CollapsibleDockState::keyed_panels`] 中，方向是其中的属性，
+    /// 因此同一方向可以放置多个面板，各自保存折叠状态、尺寸和按钮；状态中的面板通过
+    /// [`panel_key`](Self::panel_key) 访问。
+    /// 面板的 egui ID 和单独显示时保存状态的内存位置由创建时的 ID 和面板 ID 共同决定，
+    /// 使用相同 ID 创建的面板不会互相覆盖。
+    pub fn with_panel_id(mut self, id: impl Into<String>) -> Self {
+        let id = id.into();
+        let keyed = KeyedPanelState {
+            side: self.side,
+            state: self.panel_state().clone(),
+        };
+        if let Some(old_id) = &self.panel_id {
+            self.collapsible_state.keyed_panels.remove(old_id);
         }
+        self.collapsible_state
+            .keyed_panels
+            .insert(id.clone(), keyed);
+        self.state_id = self.state_id.with(("panel_id", &id));
+        self.panel_id = Some(id);
+        self
     }
 
     /// 设置 Dock 状态
@@ -242,19 +675,62 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
         self
     }
 
+    /// 设置图标注册表
+    pub fn with_icon_registry(mut self, icons: IconRegistry) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    /// 获取图标注册表，用于注册自定义图标
+    pub fn icon_registry_mut(&mut self) -> &mut IconRegistry {
+        &mut self.icons
+    }
+
+    /// 设置内置界面文本（提示和右键菜单），默认为中文
+    pub fn with_strings(mut self, strings: Strings) -> Self {
+        self.strings = strings;
+        self
+    }
+
+    /// 获取内置界面文本，用于覆盖单个条目
+    pub fn strings_mut(&mut self) -> &mut Strings {
+        &mut self.strings
+    }
+
     /// 添加折叠按钮
     pub fn add_button(mut self, button: CollapsibleButton) -> Self {
         self.buttons.push(button);
+        self.button_bindings.push(None);
+        self
+    }
+
+    /// 添加绑定到标签页的折叠按钮，点击时聚焦第一个满足条件的标签页
+    pub fn add_button_bound(
+        mut self,
+        button: CollapsibleButton,
+        predicate: impl Fn(&Tab::Tab) -> bool + 'static,
+    ) -> Self {
+        self.buttons.push(button);
+        self.button_bindings.push(Some(Box::new(predicate)));
         self
     }
 
+    /// 添加绑定到指定标签页的折叠按钮
+    pub fn add_button_for_tab(self, button: CollapsibleButton, tab: Tab::Tab) -> Self
+    where
+        Tab::Tab: PartialEq + 'static,
+    {
+        self.add_button_bound(button, move |candidate| *candidate == tab)
+    }
+
     /// 设置面板最小尺寸
     pub fn with_min_size(mut self, min_size: f32) -> Self {
-        if let Some(panel) = self.collapsible_state.panels.get_mut(&self.side) {
+        if let Some(panel) = self.collapsible_state.panel_state_mut(self.panel_key()) {
             panel.min_size = min_size;
             // 如果当前尺寸小于最小尺寸，设置一个合理的默认展开宽度
             if panel.size < min_size * 1.5 {
                 panel.size = (min_size * 2.0).max(300.0); // 确保有足够的展开宽度
+                self.default_size = panel.size;
             }
         }
         self
@@ -262,50 +738,253 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
 
     /// 设置面板最大尺寸
     pub fn with_max_size(mut self, max_size: f32) -> Self {
-        if let Some(panel) = self.collapsible_state.panels.get_mut(&self.side) {
+        if let Some(panel) = self.collapsible_state.panel_state_mut(self.panel_key()) {
             panel.max_size = Some(max_size);
         }
         self
     }
 
+    /// 设置默认尺寸，同时作为初始尺寸
+    pub fn with_default_size(mut self, size: f32) -> Self {
+        self.collapsible_state
+            .set_panel_size(self.panel_key(), size);
+        self.default_size = self.get_size();
+        self
+    }
+
+    /// 在折叠栏右键菜单末尾添加自定义菜单项
+    pub fn with_strip_menu(mut self, menu: impl FnMut(&mut Ui, Option<&str>) + 'static) -> Self {
+        self.strip_menu = Some(Box::new(menu));
+        self
+    }
+
+    /// 设置快捷键绑定表
+    pub fn with_keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// 设置通过折叠栏展开的方式
+    pub fn with_expand_mode(mut self, expand_mode: ExpandMode) -> Self {
+        self.expand_mode = expand_mode;
+        self
+    }
+
+    /// 设置是否固定面板
+    pub fn with_pinned(mut self, pinned: bool) -> Self {
+        self.set_pinned(pinned);
+        self
+    }
+
+    /// 设置未固定面板在指针离开后自动折叠的延迟（秒）
+    pub fn with_auto_collapse_delay(mut self, delay: f32) -> Self {
+        self.auto_collapse_delay = delay.max(0.0);
+        self
+    }
+
+    /// 添加响应式折叠断点，窗口进入任一断点时自动折叠，离开后恢复之前的展开状态
+    pub fn with_breakpoint(mut self, breakpoint: Breakpoint) -> Self {
+        self.breakpoints.push(breakpoint);
+        self
+    }
+
+    /// 设置响应式折叠优先级（默认 0）
+    ///
+    /// [`CollapsibleWorkspace::with_min_central_size`](crate::CollapsibleWorkspace::with_min_central_size)
+    /// 空间不足时先折叠优先级低的面板。
+    pub fn with_collapse_priority(mut self, priority: i32) -> Self {
+        self.collapse_priority = priority;
+        self
+    }
+
+    /// 在双击分隔线的循环中加入最大化：面板占用除中央区域 `min_central` 以外的全部空间
+    pub fn with_maximize(mut self, min_central: f32) -> Self {
+        self.maximize_min_central = Some(min_central.max(0.0));
+        self
+    }
+
     /// 设置是否可调整大小
     pub fn resizable(mut self, resizable: bool) -> Self {
-        if let Some(panel) = self.collapsible_state.panels.get_mut(&self.side) {
+        if let Some(panel) = self.collapsible_state.panel_state_mut(self.panel_key()) {
             panel.resizable = resizable;
         }
         self
     }
 
+    /// 设置折叠/展开动画时长（秒），为 0 时不播放动画
+    pub fn with_animation_duration(mut self, duration: f32) -> Self {
+        self.collapsible_state.animation_duration = duration.max(0.0);
+        self
+    }
+
+    /// 设置折叠/展开动画的缓动曲线
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.collapsible_state.easing = easing;
+        self
+    }
+
+    /// 设置折叠/展开动画过程中的内容过渡方式
+    pub fn with_transition(mut self, transition: TransitionMode) -> Self {
+        self.collapsible_state.transition = transition;
+        self
+    }
+
     /// 获取当前折叠状态
     pub fn is_collapsed(&self) -> bool {
-        self.collapsible_state.is_panel_collapsed(self.side)
+        self.collapsible_state.is_panel_collapsed(self.panel_key())
     }
 
     /// 切换折叠状态
     pub fn toggle(&mut self) {
-        self.collapsible_state.toggle_panel(self.side);
+        self.collapsible_state.toggle_panel(self.panel_key());
     }
 
     /// 设置折叠状态
     pub fn set_collapsed(&mut self, collapsed: bool) {
         self.collapsible_state
-            .set_panel_collapsed(self.side, collapsed);
+            .set_panel_collapsed(self.panel_key(), collapsed);
+    }
+
+    /// 面板是否因为窗口太小被自动折叠
+    pub fn is_auto_collapsed(&self) -> bool {
+        self.panel_state().auto_collapsed
+    }
+
+    /// 获取响应式折叠优先级
+    pub fn collapse_priority(&self) -> i32 {
+        self.collapse_priority
+    }
+
+    /// 面板是否展开，或者只是被自动折叠、空间足够时会恢复展开
+    pub(crate) fn wants_expanded(&self) -> bool {
+        !self.is_collapsed() || self.is_auto_collapsed()
+    }
+
+    /// 折叠时占用的尺寸，没有按钮的面板折叠后不显示
+    pub(crate) fn collapsed_size(&self) -> f32 {
+        if self.buttons.is_empty() {
+            0.0
+        } else {
+            COLLAPSED_SIZE
+        }
+    }
+
+    /// 由工作区设置：中央区域空间不足时要求折叠本面板
+    pub(crate) fn set_space_collapse(&mut self, collapse: bool) {
+        self.space_collapse = collapse;
+    }
+
+    /// 由工作区设置：面板加入工作区后状态由工作区统一保存和加载，面板不再单独读写 egui 内存
+    pub(crate) fn set_persist_state(&mut self, persist: bool) {
+        self.collapsible_state.persist_state = persist;
+    }
+
+    /// 面板是否固定
+    pub fn is_pinned(&self) -> bool {
+        self.panel_state().pinned
+    }
+
+    /// 设置是否固定面板
+    pub fn set_pinned(&mut self, pinned: bool) {
+        if let Some(panel_state) = self.collapsible_state.panel_state_mut(self.panel_key()) {
+            panel_state.pinned = pinned;
+        }
+        self.pointer_left_at = None;
     }
 
     /// 获取面板尺寸
     pub fn get_size(&self) -> f32 {
-        self.collapsible_state.get_panel_size(self.side)
+        self.collapsible_state.get_panel_size(self.panel_key())
     }
 
-    /// 设置面板尺寸
+    /// 设置面板尺寸，显示时以动画方式过渡到新尺寸
     pub fn set_size(&mut self, size: f32) {
-        self.collapsible_state.set_panel_size(self.side, size);
+        let old_size = self.get_size();
+        self.collapsible_state
+            .set_panel_size(self.panel_key(), size);
+        if self.get_size() != old_size {
+            self.size_transition = true;
+        }
+    }
+
+    /// 获取默认尺寸
+    pub fn default_size(&self) -> f32 {
+        self.default_size
+    }
+
+    /// 以动画方式恢复默认尺寸
+    pub fn reset_size(&mut self) {
+        self.set_size(self.default_size);
+    }
+
+    /// 切换到下一个尺寸，与双击分隔线相同
+    ///
+    /// 依次为用户调整的尺寸、默认尺寸、最大化（见 [`with_maximize`](Self::with_maximize)）
+    /// 和折叠；与当前尺寸相同的步骤会被跳过。折叠时恢复用户调整的尺寸，再次展开时使用。
+    pub fn cycle_size(&mut self) {
+        if self.is_collapsed() {
+            self.size_cycle = None;
+            self.set_collapsed(false);
+            return;
+        }
+
+        let current = self.get_size();
+        // 循环过程中用户调整了尺寸时重新开始
+        let cycle = self
+            .size_cycle
+            .take()
+            .filter(|cycle| (cycle.size - current).abs() < 1.0);
+        let saved = cycle.map_or(current, |cycle| cycle.saved);
+        let steps: Vec<f32> = [Some(self.default_size), self.maximized_size()]
+            .into_iter()
+            .flatten()
+            .collect();
+        let mut step = cycle.map_or(0, |cycle| cycle.step + 1);
+        while steps
+            .get(step)
+            .is_some_and(|size| (size - current).abs() < 1.0)
+        {
+            step += 1;
+        }
+
+        match steps.get(step) {
+            Some(&size) => {
+                self.set_size(size);
+                self.size_cycle = Some(SizeCycle {
+                    saved,
+                    step,
+                    size: self.get_size(),
+                });
+            }
+            None => {
+                self.collapsible_state
+                    .set_panel_size(self.panel_key(), saved);
+                self.set_collapsed(true);
+            }
+        }
+
+        let new = self.get_size();
+        if new != current {
+            self.pending_events
+                .push(PanelEvent::Resized { old: current, new });
+        }
+    }
+
+    /// 最大化时的尺寸：显示面板前剩余的空间减去中央区域的最小尺寸
+    fn maximized_size(&self) -> Option<f32> {
+        let min_central = self.maximize_min_central?;
+        let panel_state = self.panel_state();
+        let size = (self.available_extent? - min_central)
+            .max(panel_state.min_size)
+            .min(panel_state.max_size.unwrap_or(f32::INFINITY));
+        Some(size)
     }
 
-    /// 设置活动按钮索引
+    /// 设置活动按钮索引，并聚焦按钮绑定的标签页
     pub fn set_active_button(&mut self, index: usize) {
         if index < self.buttons.len() {
             self.active_button_index = Some(index);
+            self.focus_bound_tab(index);
         }
     }
 
@@ -314,110 +993,742 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
         self.active_button_index
     }
 
-    /// 显示可折叠面板
-    pub fn show(&mut self, ctx: &Context, tab_viewer: &mut Tab) -> Option<Response> {
-        // 只在第一次调用时从内存加载状态
-        if !self.state_loaded {
-            let loaded_state = CollapsibleDockState::load_from_memory(ctx, self.state_id);
-            if let Some(panel_state) = loaded_state.panels.get(&self.side) {
-                if let Some(our_panel_state) = self.collapsible_state.panels.get_mut(&self.side) {
-                    our_panel_state.collapsed = panel_state.collapsed;
-                    // 验证加载的尺寸是否合理
-                    if panel_state.size >= 100.0 {
-                        our_panel_state.size = panel_state.size;
-                    } else {
-                        // 如果加载的尺寸不合理，使用默认值
-                        our_panel_state.size = (our_panel_state.min_size * 2.0).max(300.0);
-                        println!(
-                            "Loaded invalid size {} for panel {:?}, using default {}",
-                            panel_state.size, self.side, our_panel_state.size
-                        );
-                    }
-                }
+    /// 按 ID 获取折叠栏按钮
+    pub fn button(&self, id: &str) -> Option<&CollapsibleButton> {
+        self.buttons.iter().find(|button| button.id == id)
+    }
+
+    /// 按 ID 获取可变的折叠栏按钮，可以在每帧显示前更新按钮
+    pub fn button_mut(&mut self, id: &str) -> Option<&mut CollapsibleButton> {
+        self.buttons.iter_mut().find(|button| button.id == id)
+    }
+
+    /// 设置或清除按钮的徽标，找不到按钮时返回 `false`
+    pub fn set_badge(&mut self, id: &str, badge: Option<Badge>) -> bool {
+        match self.button_mut(id) {
+            Some(button) => {
+                button.badge = badge;
+                true
             }
-            self.previous_collapsed = self.is_collapsed();
-            self.state_loaded = true;
+            None => false,
         }
+    }
 
-        let is_collapsed = self.is_collapsed();
-        self.previous_collapsed = is_collapsed;
+    /// 按显示顺序获取折叠栏按钮的 ID
+    pub fn button_order(&self) -> Vec<&str> {
+        self.button_display_order()
+            .into_iter()
+            .map(|index| self.buttons[index].id.as_str())
+            .collect()
+    }
 
-        // 如果完全折叠且没有按钮，就不显示面板
-        if is_collapsed && self.buttons.is_empty() {
-            return None;
+    /// 按按钮 ID 设置折叠栏按钮的显示顺序，未列出的按钮按添加顺序排在最后
+    pub fn set_button_order<S: Into<String>>(&mut self, ids: impl IntoIterator<Item = S>) {
+        if let Some(panel_state) = self.collapsible_state.panel_state_mut(self.panel_key()) {
+            panel_state.button_order = ids.into_iter().map(Into::into).collect();
         }
+    }
 
-        // 创建面板
-        let panel_response = match self.side {
-            PanelSide::Left => self.show_left_panel(ctx, tab_viewer, is_collapsed),
-            PanelSide::Right => self.show_right_panel(ctx, tab_viewer, is_collapsed),
-            PanelSide::Top => self.show_top_panel(ctx, tab_viewer, is_collapsed),
-            PanelSide::Bottom => self.show_bottom_panel(ctx, tab_viewer, is_collapsed),
-        };
+    /// 恢复按添加顺序显示折叠栏按钮
+    pub fn reset_button_order(&mut self) {
+        self.set_button_order(Vec::<String>::new());
+    }
 
-        // 保存状态
-        self.collapsible_state.save_to_memory(ctx, self.state_id);
+    /// 按钮是否被隐藏
+    pub fn is_button_hidden(&self, id: &str) -> bool {
+        self.panel_state()
+            .hidden_buttons
+            .iter()
+            .any(|hidden| hidden == id)
+    }
 
-        panel_response
+    /// 隐藏或重新显示折叠栏按钮，找不到按钮时返回 `false`
+    pub fn set_button_hidden(&mut self, id: &str, hidden: bool) -> bool {
+        if self.button(id).is_none() {
+            return false;
+        }
+        if let Some(panel_state) = self.collapsible_state.panel_state_mut(self.panel_key()) {
+            panel_state
+                .hidden_buttons
+                .retain(|hidden_id| hidden_id != id);
+            if hidden {
+                panel_state.hidden_buttons.push(id.to_owned());
+            }
+        }
+        true
     }
 
-    /// 统一的面板渲染方法
-    fn show_panel_unified(
-        &mut self,
-        ctx: &Context,
-        tab_viewer: &mut Tab,
-        is_collapsed: bool,
-    ) -> Option<Response> {
-        let side_name = match self.side {
-            PanelSide::Left => "left",
-            PanelSide::Right => "right",
-            PanelSide::Top => "top",
-            PanelSide::Bottom => "bottom",
-        };
+    /// 重新显示所有隐藏的按钮
+    pub fn unhide_all_buttons(&mut self) {
+        if let Some(panel_state) = self.collapsible_state.panel_state_mut(self.panel_key()) {
+            panel_state.hidden_buttons.clear();
+        }
+    }
 
-        // 使用更平滑的动画
-        let animation_id = self.state_id.with(format!("{}_animation", side_name));
-        let target_value = if is_collapsed { 0.0 } else { 1.0 };
-        let animation_value = ctx.animate_value_with_time(
-            animation_id,
-            target_value,
-            0.2, // 200ms 的动画时间
-        );
+    /// 获取面板方向
+    pub fn side(&self) -> PanelSide {
+        self.side
+    }
 
-        let saved_size = self.get_size();
+    /// 获取创建面板时的方向
+    pub fn home_side(&self) -> PanelSide {
+        self.home_side
+    }
 
-        // 动态计算折叠宽度：根据图标大小和边距
-        let icon_size = 14.0;
-        let padding = 6.0; // 左右各3px边距，提供适当的点击区域
-        let collapsed_size = icon_size + padding * 2.0; // 26px，更紧凑的设计
+    /// 获取面板 ID
+    pub fn panel_id(&self) -> Option<&str> {
+        self.panel_id.as_deref()
+    }
 
-        let panel_state = &self.collapsible_state.panels[&self.side];
+    /// 获取面板状态在 [`CollapsibleDockState`] 中的键
+    pub fn panel_key(&self) -> PanelKey {
+        match &self.panel_id {
+            Some(id) => PanelKey::Id(id.clone()),
+            None => PanelKey::Side(self.side),
+        }
+    }
 
-        // 确保saved_size是合理的，如果不合理则使用默认值
-        let validated_saved_size = if saved_size < 100.0 {
-            let default_size = (panel_state.min_size * 2.0).max(300.0);
-            println!(
-                "Invalid saved_size {} for panel {:?}, using default {}",
-                saved_size, self.side, default_size
-            );
-            default_size
-        } else {
-            saved_size
-        };
+    /// 获取面板在工作区中对应的 [`DockTarget`]
+    pub fn dock_target(&self) -> DockTarget {
+        match &self.panel_id {
+            Some(id) => DockTarget::KeyedPanel(id.clone()),
+            None => DockTarget::Panel(self.side),
+        }
+    }
 
-        // 计算动画中的面板宽度
-        let animated_size = if animation_value < 0.01 {
-            collapsed_size
-        } else if animation_value > 0.99 {
-            validated_saved_size
-        } else {
-            // 使用缓动函数让动画更平滑
-            let eased = ease_in_out_cubic(animation_value);
-            collapsed_size + (validated_saved_size - collapsed_size) * eased
-        };
+    /// 在面板标签栏中按下、还没有松开指针的标签页
+    pub(crate) fn pressed_tab(&self, ctx: &Context) -> Option<TabPath> {
+        self.pressed_tab.get(ctx)
+    }
 
-        // 🔧 关键修复：为展开和折叠状态使用不同的面板ID，避免状态冲突
-        let egui_panel_id = if is_collapsed {
+    /// 将面板移动到另一侧，按钮、Dock 状态和折叠状态保持不变，展开的面板以动画方式出现
+    ///
+    /// 在宽度和高度之间移动时，尺寸按窗口大小等比例换算。新方向随面板状态持久化。
+    /// 在 [`CollapsibleWorkspace`](crate::CollapsibleWorkspace) 中，面板会移动到新方向的槽位，
+    /// 与原来在该方向的面板交换位置。
+    pub fn set_side(&mut self, side: PanelSide) {
+        if side == self.side {
+            return;
+        }
+        let old_side = self.side;
+        self.move_state_to(side);
+
+        if let (Some(content_size), Some(panel_state)) = (
+            self.content_size,
+            self.collapsible_state.panel_state_mut(self.panel_key()),
+        ) {
+            let old_extent = side_extent(old_side, content_size);
+            let new_extent = side_extent(side, content_size);
+            if old_extent > 0.0 && old_extent != new_extent {
+                let size = (panel_state.size * new_extent / old_extent)
+                    .max(panel_state.min_size)
+                    .min(panel_state.max_size.unwrap_or(f32::INFINITY));
+                panel_state.size = size;
+            }
+        }
+        self.side_transition = true;
+        self.pending_events.push(PanelEvent::Moved {
+            from: old_side,
+            to: side,
+        });
+    }
+
+    /// 切换方向并记录方向覆盖，不换算尺寸
+    ///
+    /// 设置了面板 ID 的面板只更新状态中记录的方向。
+    fn move_state_to(&mut self, side: PanelSide) {
+        if let Some(id) = &self.panel_id {
+            if let Some(keyed) = self.collapsible_state.keyed_panels.get_mut(id) {
+                keyed.side = side;
+            }
+        } else {
+            let panel_state = self.panel_state().clone();
+            self.collapsible_state.panels.insert(side, panel_state);
+            if side == self.home_side {
+                self.collapsible_state
+                    .side_overrides
+                    .remove(&self.home_side);
+            } else {
+                self.collapsible_state
+                    .side_overrides
+                    .insert(self.home_side, side);
+            }
+        }
+        self.side = side;
+        self.button_drag = None;
+        self.overlay_open = false;
+    }
+
+    /// 获取当前面板状态
+    pub fn panel_state(&self) -> &PanelState {
+        match &self.panel_id {
+            Some(id) => &self.collapsible_state.keyed_panels[id].state,
+            None => &self.collapsible_state.panels[&self.side],
+        }
+    }
+
+    /// 从已保存的状态恢复折叠状态和尺寸
+    ///
+    /// 调用后 `show` 不再从 egui 内存加载状态。
+    pub fn restore_state(&mut self, loaded_state: &CollapsibleDockState) {
+        let (side, saved) = self.saved_placement(loaded_state);
+        if side != self.side {
+            self.move_state_to(side);
+        }
+
+        if let Some(panel_state) = saved {
+            if let Some(our_panel_state) = self.collapsible_state.panel_state_mut(self.panel_key())
+            {
+                our_panel_state.collapsed = panel_state.collapsed;
+                our_panel_state.pinned = panel_state.pinned;
+                our_panel_state.button_order = panel_state.button_order.clone();
+                our_panel_state.hidden_buttons = panel_state.hidden_buttons.clone();
+                our_panel_state.auto_collapsed = panel_state.auto_collapsed;
+                // 验证加载的尺寸是否合理
+                if panel_state.size >= 100.0 {
+                    our_panel_state.size = panel_state.size;
+                } else {
+                    // 如果加载的尺寸不合理，使用默认值
+                    our_panel_state.size = (our_panel_state.min_size * 2.0).max(300.0);
+                }
+            }
+        }
+        self.previous_collapsed = self.is_collapsed();
+        self.state_loaded = true;
+    }
+
+    /// 以动画方式过渡到指定状态中本面板的方向、折叠状态和尺寸
+    ///
+    /// 方向通过 [`set_side`](Self::set_side) 改变；方向和尺寸的变化由下一次 `show`
+    /// 报告为 `Moved` 和 `Resized`。
+    pub fn animate_to_state(&mut self, state: &CollapsibleDockState) {
+        let (side, saved) = self.saved_placement(state);
+        let old_size = self.get_size();
+        self.set_side(side);
+
+        if let Some(panel_state) = saved {
+            self.set_collapsed(panel_state.collapsed);
+            self.set_size(panel_state.size);
+            self.set_pinned(panel_state.pinned);
+            self.set_button_order(panel_state.button_order.iter().cloned());
+            if let Some(our_panel_state) = self.collapsible_state.panel_state_mut(self.panel_key())
+            {
+                our_panel_state.hidden_buttons = panel_state.hidden_buttons.clone();
+                our_panel_state.auto_collapsed = panel_state.auto_collapsed;
+            }
+        }
+
+        let new_size = self.get_size();
+        if new_size != old_size {
+            self.pending_events.push(PanelEvent::Resized {
+                old: old_size,
+                new: new_size,
+            });
+        }
+    }
+
+    /// 把本面板的方向和状态写入 `state`
+    ///
+    /// 设置了面板 ID 的面板写入 `keyed_panels`，其余面板按方向写入 `panels`，
+    /// 移动过的面板同时记录 `side_overrides`。
+    pub fn write_state(&self, state: &mut CollapsibleDockState) {
+        let panel_state = self.panel_state().clone();
+        match &self.panel_id {
+            Some(id) => {
+                state.keyed_panels.insert(
+                    id.clone(),
+                    KeyedPanelState {
+                        side: self.side,
+                        state: panel_state,
+                    },
+                );
+            }
+            None => {
+                state.panels.insert(self.side, panel_state);
+                if self.side != self.home_side {
+                    state.side_overrides.insert(self.home_side, self.side);
+                }
+            }
+        }
+    }
+
+    /// 在保存的状态中查找本面板的方向和状态
+    fn saved_placement<'a>(
+        &self,
+        state: &'a CollapsibleDockState,
+    ) -> (PanelSide, Option<&'a PanelState>) {
+        match &self.panel_id {
+            Some(id) => match state.keyed_panels.get(id) {
+                Some(keyed) => (keyed.side, Some(&keyed.state)),
+                None => (self.side, None),
+            },
+            None => {
+                let side = state
+                    .side_overrides
+                    .get(&self.home_side)
+                    .copied()
+                    .unwrap_or(self.home_side);
+                (side, state.panels.get(&side))
+            }
+        }
+    }
+
+    /// 获取 Dock 状态
+    pub fn dock_state(&self) -> &DockState<Tab::Tab> {
+        &self.dock_state
+    }
+
+    /// 获取可变的 Dock 状态
+    pub fn dock_state_mut(&mut self) -> &mut DockState<Tab::Tab> {
+        &mut self.dock_state
+    }
+
+    /// 替换 Dock 状态
+    pub fn set_dock_state(&mut self, dock_state: DockState<Tab::Tab>) {
+        self.dock_state = dock_state;
+        self.last_focused_button = None;
+    }
+
+    /// 浮动面板是否打开
+    pub fn is_overlay_open(&self) -> bool {
+        self.overlay_open
+    }
+
+    /// 打开或关闭浮动面板，只在面板折叠时生效
+    pub fn set_overlay_open(&mut self, open: bool) {
+        self.overlay_open = open && self.is_collapsed();
+    }
+
+    /// 获取快捷键绑定表
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// 获取可变的快捷键绑定表
+    pub fn keymap_mut(&mut self) -> &mut Keymap {
+        &mut self.keymap
+    }
+
+    /// 检查快捷键绑定表内部的冲突
+    pub fn keymap_conflicts(&self) -> Vec<KeymapConflict> {
+        find_conflicts([(self.side, &self.keymap)])
+    }
+
+    /// 获取最近一次 `show` 产生的事件
+    pub fn events(&self) -> &[PanelEvent] {
+        &self.events
+    }
+
+    /// 取出最近一次 `show` 产生的事件
+    pub fn take_events(&mut self) -> Vec<PanelEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// 显示可折叠面板，并返回本帧产生的事件
+    pub fn show_with_events(
+        &mut self,
+        ctx: &Context,
+        tab_viewer: &mut Tab,
+    ) -> (Option<Response>, Vec<PanelEvent>) {
+        let response = self.show(ctx, tab_viewer);
+        (response, self.take_events())
+    }
+
+    /// 显示可折叠面板
+    ///
+    /// 本帧的事件包括上一次显示之后在 `show` 之外产生的事件。
+    pub fn show(&mut self, ctx: &Context, tab_viewer: &mut Tab) -> Option<Response> {
+        self.events = std::mem::take(&mut self.pending_events);
+        let response = self.show_panel(ctx, tab_viewer);
+        // 本帧内执行的操作（如右键菜单移动面板）产生的事件
+        self.events.append(&mut self.pending_events);
+        response
+    }
+
+    /// 加载状态并显示面板，返回面板的响应
+    fn show_panel(&mut self, ctx: &Context, tab_viewer: &mut Tab) -> Option<Response> {
+        // 只在第一次调用时从内存加载状态，没有已保存的状态时保留构建时的配置
+        if !self.state_loaded {
+            let loaded_state = self
+                .collapsible_state
+                .persist_state
+                .then(|| CollapsibleDockState::try_load_from_memory(ctx, self.state_id))
+                .flatten();
+            match loaded_state {
+                Some(loaded_state) => self.restore_state(&loaded_state),
+                None => {
+                    self.previous_collapsed = self.is_collapsed();
+                    self.state_loaded = true;
+                }
+            }
+        }
+
+        self.handle_shortcuts(ctx);
+        self.content_size = Some(ctx.content_rect().size());
+        self.apply_responsive_collapse(ctx.content_rect().size());
+
+        // 检测外部（如 `toggle`、快捷键）造成的折叠状态变化
+        self.detect_state_changes();
+        let is_collapsed = self.is_collapsed();
+
+        // 如果完全折叠且没有按钮，就不显示面板
+        if is_collapsed && self.buttons.is_empty() {
+            return None;
+        }
+
+        // 创建面板
+        let panel_response = match self.side {
+            PanelSide::Left => self.show_left_panel(ctx, tab_viewer, is_collapsed),
+            PanelSide::Right => self.show_right_panel(ctx, tab_viewer, is_collapsed),
+            PanelSide::Top => self.show_top_panel(ctx, tab_viewer, is_collapsed),
+            PanelSide::Bottom => self.show_bottom_panel(ctx, tab_viewer, is_collapsed),
+        };
+
+        if self.moving_panel {
+            self.paint_move_targets(ctx);
+        }
+
+        // 右键菜单和拖动面板的操作可能改变面板方向，等面板渲染结束后再执行
+        if let Some(action) = self.strip_action.take() {
+            self.run_strip_action(action);
+        }
+
+        if let Some(panel_rect) = panel_response.as_ref().map(|response| response.rect) {
+            // 浮动面板覆盖在中央区域上方，不参与布局
+            self.show_overlay(ctx, tab_viewer, panel_rect);
+            self.auto_collapse_unpinned(ctx, panel_rect);
+        }
+
+        // 按钮点击可能在本帧改变折叠状态
+        self.detect_state_changes();
+
+        // 保存状态
+        self.collapsible_state.save_to_memory(ctx, self.state_id);
+
+        panel_response
+    }
+
+    /// 根据断点和工作区的空间要求自动折叠面板，条件解除后恢复之前的展开状态
+    ///
+    /// 只在进入断点时折叠一次，用户随后手动展开的面板不会被再次折叠。
+    fn apply_responsive_collapse(&mut self, window_size: Vec2) {
+        let collapse = self.space_collapse
+            || self
+                .breakpoints
+                .iter()
+                .any(|breakpoint| breakpoint.applies(window_size));
+        let entered = collapse && !self.responsive_collapse;
+        self.responsive_collapse = collapse;
+
+        let key = self.panel_key();
+        let Some(panel_state) = self.collapsible_state.panel_state_mut(key) else {
+            return;
+        };
+        if panel_state.auto_collapsed && (!collapse || !panel_state.collapsed) {
+            // 条件解除，或者用户已经手动展开
+            panel_state.auto_collapsed = false;
+            panel_state.collapsed = false;
+        } else if entered && !panel_state.collapsed {
+            panel_state.auto_collapsed = true;
+            panel_state.collapsed = true;
+            self.overlay_open = false;
+        }
+    }
+
+    /// 显示浮动面板，点击外部区域或按下 Escape 时关闭
+    fn show_overlay(&mut self, ctx: &Context, tab_viewer: &mut Tab, strip_rect: egui::Rect) {
+        // 面板停靠展开后不再需要浮动面板
+        if !self.is_collapsed() {
+            self.set_overlay_open(false);
+        }
+        if !self.overlay_open {
+            return;
+        }
+
+        let overlay_rect = self.overlay_rect(strip_rect);
+        let escape_pressed =
+            ctx.input_mut(|input| input.consume_key(egui::Modifiers::NONE, egui::Key::Escape));
+        let pressed_at = ctx.input(|input| {
+            input
+                .pointer
+                .any_pressed()
+                .then(|| input.pointer.interact_pos())
+                .flatten()
+        });
+        // 浮动面板中打开的弹出菜单、下拉框等超出浮动面板区域，在其中按下不算点击外部
+        let clicked_outside = pressed_at.is_some_and(|pos| {
+            !overlay_rect.contains(pos)
+                && !strip_rect.contains(pos)
+                && !egui::Popup::is_any_open(ctx)
+                && ctx
+                    .layer_id_at(pos)
+                    .is_none_or(|layer| layer.order < egui::Order::Foreground)
+        });
+        if escape_pressed || clicked_outside {
+            self.set_overlay_open(false);
+            return;
+        }
+
+        egui::Area::new(self.state_id.with("overlay"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(overlay_rect.min)
+            .show(ctx, |ui| {
+                ui.set_min_size(overlay_rect.size());
+                ui.set_max_size(overlay_rect.size());
+
+                let visuals = &ui.style().visuals;
+                let shadow = visuals.popup_shadow.as_shape(overlay_rect, 0.0);
+                let fill = visuals.panel_fill;
+                ui.painter().add(shadow);
+                ui.painter().rect_filled(overlay_rect, 0.0, fill);
+
+                self.show_expanded_content(ui, tab_viewer);
+            });
+    }
+
+    /// 未固定的面板在失去焦点（点击外部）或指针离开超过延迟后自动折叠
+    fn auto_collapse_unpinned(&mut self, ctx: &Context, panel_rect: egui::Rect) {
+        if self.is_collapsed() || self.is_pinned() {
+            self.pointer_left_at = None;
+            return;
+        }
+
+        // 分隔线的拖动区域超出面板边缘，按下分隔线调整尺寸不算点击外部
+        let panel_rect = panel_rect.expand(ctx.style().interaction.resize_grab_radius_side);
+        let (now, pointer_inside, clicked_outside) = ctx.input(|input| {
+            let pointer_inside = input
+                .pointer
+                .hover_pos()
+                .is_some_and(|pos| panel_rect.contains(pos));
+            let clicked_outside = input.pointer.any_pressed()
+                && input
+                    .pointer
+                    .interact_pos()
+                    .is_some_and(|pos| !panel_rect.contains(pos));
+            (input.time, pointer_inside, clicked_outside)
+        });
+
+        if pointer_inside {
+            self.pointer_left_at = None;
+            return;
+        }
+        let left_at = *self.pointer_left_at.get_or_insert(now);
+        let away_for = (now - left_at) as f32;
+        if clicked_outside || away_for >= self.auto_collapse_delay {
+            self.pointer_left_at = None;
+            self.set_collapsed(true);
+        } else {
+            ctx.request_repaint_after_secs(self.auto_collapse_delay - away_for);
+        }
+    }
+
+    /// 计算浮动面板区域：贴住折叠栏朝向中央区域的一侧
+    fn overlay_rect(&self, strip_rect: egui::Rect) -> egui::Rect {
+        let size = self.get_size();
+        match self.side {
+            PanelSide::Left => egui::Rect::from_min_size(
+                strip_rect.right_top(),
+                Vec2::new(size, strip_rect.height()),
+            ),
+            PanelSide::Right => egui::Rect::from_min_size(
+                strip_rect.left_top() - Vec2::new(size, 0.0),
+                Vec2::new(size, strip_rect.height()),
+            ),
+            PanelSide::Top => egui::Rect::from_min_size(
+                strip_rect.left_bottom(),
+                Vec2::new(strip_rect.width(), size),
+            ),
+            PanelSide::Bottom => egui::Rect::from_min_size(
+                strip_rect.left_top() - Vec2::new(0.0, size),
+                Vec2::new(strip_rect.width(), size),
+            ),
+        }
+    }
+
+    /// 处理快捷键，绑定了多个操作的快捷键不执行任何操作并产生冲突事件
+    fn handle_shortcuts(&mut self, ctx: &Context) {
+        for shortcut in self.keymap.shortcuts_by_priority() {
+            let pressed =
+                ctx.input_mut(|input| input.consume_shortcut(&shortcut.to_keyboard_shortcut()));
+            if !pressed {
+                continue;
+            }
+
+            if !self.run_shortcut(shortcut) {
+                self.events.push(PanelEvent::ShortcutConflict(shortcut));
+            }
+        }
+    }
+
+    /// 执行快捷键绑定的操作，同一快捷键绑定了多个操作时不执行并返回 `false`
+    pub(crate) fn run_shortcut(&mut self, shortcut: Shortcut) -> bool {
+        let actions: Vec<KeyAction> = self.keymap.actions(shortcut).collect();
+        match actions.as_slice() {
+            [action] => {
+                self.run_key_action(*action);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 执行快捷键操作
+    fn run_key_action(&mut self, action: KeyAction) {
+        match action {
+            KeyAction::TogglePanel => self.toggle(),
+            KeyAction::FocusButton(position) => {
+                if let Some(&index) = self.visible_button_order().get(position) {
+                    self.activate_button(index);
+                }
+            }
+            KeyAction::NextTab => self.cycle_tab(true),
+            KeyAction::PreviousTab => self.cycle_tab(false),
+        }
+    }
+
+    /// 在焦点区域内循环切换活动标签页
+    ///
+    /// 还没有焦点区域时（例如新建的 `DockState`）使用第一个有标签页的区域并聚焦它。
+    fn cycle_tab(&mut self, forward: bool) {
+        let Some(leaf) = self.dock_state.focused_leaf().or_else(|| {
+            let (leaf, _) = self.dock_state.iter_all_tabs().next()?;
+            self.dock_state.set_focused_node_and_surface(leaf);
+            Some(leaf)
+        }) else {
+            return;
+        };
+        // DockState 不直接提供活动标签页的索引，通过地址在焦点区域的标签页中定位
+        let Some(active) = self
+            .dock_state
+            .find_active_focused()
+            .map(|(_, tab)| tab as *const Tab::Tab)
+        else {
+            return;
+        };
+        let leaf_tabs: Vec<*const Tab::Tab> = self
+            .dock_state
+            .iter_all_tabs()
+            .filter(|(location, _)| *location == leaf)
+            .map(|(_, tab)| tab as *const Tab::Tab)
+            .collect();
+        let Some(index) = leaf_tabs.iter().position(|tab| std::ptr::eq(*tab, active)) else {
+            return;
+        };
+
+        let count = leaf_tabs.len();
+        let next = if forward {
+            (index + 1) % count
+        } else {
+            (index + count - 1) % count
+        };
+        self.dock_state
+            .set_active_tab((leaf.0, leaf.1, TabIndex(next)));
+    }
+
+    /// 比较前一帧的折叠和浮动面板状态，产生对应的事件
+    fn detect_state_changes(&mut self) {
+        let is_collapsed = self.is_collapsed();
+        if is_collapsed != self.previous_collapsed {
+            self.events.push(if is_collapsed {
+                PanelEvent::Collapsed
+            } else {
+                PanelEvent::Expanded
+            });
+            self.previous_collapsed = is_collapsed;
+        }
+
+        if self.overlay_open != self.previous_overlay_open {
+            self.events.push(if self.overlay_open {
+                PanelEvent::OverlayOpened
+            } else {
+                PanelEvent::OverlayClosed
+            });
+            self.previous_overlay_open = self.overlay_open;
+        }
+    }
+
+    /// 统一的面板渲染方法
+    fn show_panel_unified(
+        &mut self,
+        ctx: &Context,
+        tab_viewer: &mut Tab,
+        is_collapsed: bool,
+    ) -> Option<Response> {
+        let side_name = match self.side {
+            PanelSide::Left => "left",
+            PanelSide::Right => "right",
+            PanelSide::Top => "top",
+            PanelSide::Bottom => "bottom",
+        };
+
+        self.available_extent = Some(side_extent(self.side, ctx.available_rect().size()));
+
+        // 使用更平滑的动画
+        let animation_id = self.state_id.with(format!("{}_animation", side_name));
+        if std::mem::take(&mut self.side_transition) {
+            // 刚移动到这一侧：从折叠状态开始播放展开动画
+            animate_to(ctx, animation_id, 0.0, 0.0);
+        }
+        let target_value = if is_collapsed { 0.0 } else { 1.0 };
+        let animation_value = animate_progress(
+            ctx,
+            animation_id,
+            !is_collapsed,
+            self.collapsible_state.animation_duration,
+        );
+
+        // 检测动画结束
+        let is_animating = animation_value != target_value;
+        if self.was_animating && !is_animating {
+            self.events.push(PanelEvent::AnimationFinished);
+        }
+        self.was_animating = is_animating;
+
+        let saved_size = self.get_size();
+
+        let collapsed_size = COLLAPSED_SIZE;
+
+        // 下面会修改尺寸动画状态，先复制面板状态
+        let panel_state = self.panel_state().clone();
+
+        // 从内存加载的尺寸可能无效（小于最小尺寸），此时使用默认值
+        let validated_saved_size = if saved_size.is_finite() && saved_size >= panel_state.min_size {
+            saved_size
+        } else {
+            (panel_state.min_size * 2.0).max(300.0)
+        };
+
+        // 程序设置的新尺寸以动画方式过渡，其余情况直接使用保存的尺寸
+        let size_animation_id = self.state_id.with(format!("{}_size_animation", side_name));
+        let size_animating = self.size_transition;
+        let validated_saved_size = if size_animating {
+            let size = animate_to(
+                ctx,
+                size_animation_id,
+                validated_saved_size,
+                self.collapsible_state.animation_duration,
+            );
+            if size == validated_saved_size {
+                self.size_transition = false;
+            }
+            size
+        } else {
+            animate_to(ctx, size_animation_id, validated_saved_size, 0.0)
+        };
+
+        // 计算动画中的面板宽度
+        let animated_size = if animation_value < 0.01 {
+            collapsed_size
+        } else if animation_value > 0.99 {
+            validated_saved_size
+        } else {
+            // 使用缓动函数让动画更平滑
+            let eased = self.collapsible_state.easing.apply(animation_value);
+            collapsed_size + (validated_saved_size - collapsed_size) * eased
+        };
+
+        // 🔧 关键修复：为展开和折叠状态使用不同的面板ID，避免状态冲突
+        let egui_panel_id = if is_collapsed {
             self.state_id.with(format!("{}_collapsed", side_name))
         } else {
             self.state_id.with(format!("{}_expanded", side_name))
@@ -434,7 +1745,10 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
         let panel_response = match self.side {
             PanelSide::Left => {
                 // 动态控制resizable：只有在完全展开且用户配置允许时才启用
-                let is_resizable = !is_collapsed && panel_state.resizable && animation_value > 0.99;
+                let is_resizable = !is_collapsed
+                    && panel_state.resizable
+                    && animation_value > 0.99
+                    && !size_animating;
 
                 let mut panel = egui::SidePanel::left(egui_panel_id)
                     .frame(frame)
@@ -455,24 +1769,16 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
                 }
 
                 panel.show(ctx, |ui| {
-                    // 根据动画进度决定显示内容
-                    if animation_value < 0.3 {
-                        // 折叠状态
-                        self.show_collapsed_content(ui, animation_value);
-                    } else if animation_value > 0.7 {
-                        // 展开状态
-                        self.show_expanded_content(ui, tab_viewer);
-                    } else {
-                        // 过渡状态 - 显示加载或空白
-                        ui.centered_and_justified(|ui| {
-                            ui.spinner();
-                        });
-                    }
+                    // 根据动画进度和过渡方式决定显示内容
+                    self.show_panel_content(ui, tab_viewer, animation_value, validated_saved_size);
                 })
             }
             PanelSide::Right => {
                 // 动态控制resizable：只有在完全展开且用户配置允许时才启用
-                let is_resizable = !is_collapsed && panel_state.resizable && animation_value > 0.99;
+                let is_resizable = !is_collapsed
+                    && panel_state.resizable
+                    && animation_value > 0.99
+                    && !size_animating;
 
                 let mut panel = egui::SidePanel::right(egui_panel_id)
                     .frame(frame)
@@ -493,24 +1799,16 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
                 }
 
                 panel.show(ctx, |ui| {
-                    // 根据动画进度决定显示内容
-                    if animation_value < 0.3 {
-                        // 折叠状态
-                        self.show_collapsed_content(ui, animation_value);
-                    } else if animation_value > 0.7 {
-                        // 展开状态
-                        self.show_expanded_content(ui, tab_viewer);
-                    } else {
-                        // 过渡状态 - 显示加载或空白
-                        ui.centered_and_justified(|ui| {
-                            ui.spinner();
-                        });
-                    }
+                    // 根据动画进度和过渡方式决定显示内容
+                    self.show_panel_content(ui, tab_viewer, animation_value, validated_saved_size);
                 })
             }
             PanelSide::Top => {
                 // 动态控制resizable：只有在完全展开且用户配置允许时才启用
-                let is_resizable = !is_collapsed && panel_state.resizable && animation_value > 0.99;
+                let is_resizable = !is_collapsed
+                    && panel_state.resizable
+                    && animation_value > 0.99
+                    && !size_animating;
 
                 let mut panel = egui::TopBottomPanel::top(egui_panel_id)
                     .frame(frame)
@@ -531,24 +1829,16 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
                 }
 
                 panel.show(ctx, |ui| {
-                    // 根据动画进度决定显示内容
-                    if animation_value < 0.3 {
-                        // 折叠状态
-                        self.show_collapsed_content(ui, animation_value);
-                    } else if animation_value > 0.7 {
-                        // 展开状态
-                        self.show_expanded_content(ui, tab_viewer);
-                    } else {
-                        // 过渡状态 - 显示加载或空白
-                        ui.centered_and_justified(|ui| {
-                            ui.spinner();
-                        });
-                    }
+                    // 根据动画进度和过渡方式决定显示内容
+                    self.show_panel_content(ui, tab_viewer, animation_value, validated_saved_size);
                 })
             }
             PanelSide::Bottom => {
                 // 动态控制resizable：只有在完全展开且用户配置允许时才启用
-                let is_resizable = !is_collapsed && panel_state.resizable && animation_value > 0.99;
+                let is_resizable = !is_collapsed
+                    && panel_state.resizable
+                    && animation_value > 0.99
+                    && !size_animating;
 
                 let mut panel = egui::TopBottomPanel::bottom(egui_panel_id)
                     .frame(frame)
@@ -562,48 +1852,370 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
                         .max_height(panel_state.max_size.unwrap_or(f32::INFINITY))
                         .default_height(validated_saved_size);
                 } else {
-                    panel = panel
-                        .min_height(animated_size)
-                        .max_height(animated_size)
-                        .default_height(animated_size);
+                    panel = panel
+                        .min_height(animated_size)
+                        .max_height(animated_size)
+                        .default_height(animated_size);
+                }
+
+                panel.show(ctx, |ui| {
+                    // 根据动画进度和过渡方式决定显示内容
+                    self.show_panel_content(ui, tab_viewer, animation_value, validated_saved_size);
+                })
+            }
+        };
+
+        // 保存用户调整的尺寸（动画过程中的尺寸不是用户调整的结果）
+        if !is_collapsed && animation_value > 0.99 && !size_animating {
+            let resize_id = egui_panel_id.with("__resize");
+            // 拖动分隔线时尺寸随拖动保存，`Resized` 在松开后由 `handle_resize_snap` 发出
+            let resizing = ctx
+                .read_response(resize_id)
+                .is_some_and(|resize| resize.dragged() || resize.drag_stopped());
+            let actual_size = match self.side {
+                PanelSide::Left | PanelSide::Right => panel_response.response.rect.width(),
+                PanelSide::Top | PanelSide::Bottom => panel_response.response.rect.height(),
+            };
+
+            // 只有当尺寸发生显著变化时才保存，避免频繁的微小调整
+            let current_saved_size = self.get_size();
+            if (actual_size - current_saved_size).abs() > 5.0 {
+                self.collapsible_state
+                    .set_panel_size(self.panel_key(), actual_size);
+                let new_size = self.get_size();
+                if new_size != current_saved_size && !resizing {
+                    self.events.push(PanelEvent::Resized {
+                        old: current_saved_size,
+                        new: new_size,
+                    });
+                }
+            }
+
+            self.handle_resize_snap(ctx, resize_id, panel_response.response.rect);
+
+            // egui 的分隔线只感应拖动，双击需要从输入中判断
+            let separator_hovered = ctx
+                .read_response(resize_id)
+                .is_some_and(|resize| resize.hovered());
+            if separator_hovered
+                && ctx.input(|input| {
+                    input
+                        .pointer
+                        .button_double_clicked(egui::PointerButton::Primary)
+                })
+            {
+                self.cycle_size();
+            }
+        }
+
+        Some(panel_response.response)
+    }
+
+    /// 拖动调整尺寸的边缘到折叠阈值以下时提示折叠，松开后折叠面板并恢复拖动前的尺寸；
+    /// 未折叠时在松开后发出一次 `Resized`
+    fn handle_resize_snap(&mut self, ctx: &Context, resize_id: Id, panel_rect: egui::Rect) {
+        let resize = ctx.read_response(resize_id);
+        let dragging = resize
+            .as_ref()
+            .is_some_and(|resize| resize.dragged() || resize.drag_stopped());
+        if !dragging {
+            self.size_before_resize = Some(self.get_size());
+            return;
+        }
+
+        let snap_size = self.panel_state().min_size * COLLAPSE_SNAP_RATIO;
+        let snap = ctx
+            .pointer_latest_pos()
+            .is_some_and(|pos| edge_distance(self.side, panel_rect, pos) < snap_size);
+        let drag_stopped = resize.is_some_and(|resize| resize.drag_stopped());
+        let current = self.get_size();
+        let before = self.size_before_resize.unwrap_or(current);
+
+        if !snap {
+            if drag_stopped && current != before {
+                self.events.push(PanelEvent::Resized {
+                    old: before,
+                    new: current,
+                });
+            }
+            return;
+        }
+
+        if drag_stopped {
+            self.collapsible_state
+                .set_panel_size(self.panel_key(), before);
+            self.set_collapsed(true);
+        } else {
+            // 松开后会折叠：把面板显示为半透明的选中色作为提示
+            let painter = ctx.layer_painter(egui::LayerId::new(
+                egui::Order::Foreground,
+                self.state_id.with("collapse_snap"),
+            ));
+            let selection = ctx.style().visuals.selection;
+            painter.rect_filled(panel_rect, 0.0, selection.bg_fill.gamma_multiply(0.2));
+        }
+    }
+
+    /// 拖动折叠栏的内侧边缘展开面板，松开后面板直接展开到拖动的尺寸；双击展开到之前的尺寸
+    fn handle_strip_edge_drag(&mut self, ui: &Ui, strip_rect: egui::Rect) {
+        let grab = ui.style().interaction.resize_grab_radius_side;
+        let edge_rect = match self.side {
+            PanelSide::Left => egui::Rect::from_x_y_ranges(
+                strip_rect.right() - grab..=strip_rect.right(),
+                strip_rect.y_range(),
+            ),
+            PanelSide::Right => egui::Rect::from_x_y_ranges(
+                strip_rect.left()..=strip_rect.left() + grab,
+                strip_rect.y_range(),
+            ),
+            PanelSide::Top => egui::Rect::from_x_y_ranges(
+                strip_rect.x_range(),
+                strip_rect.bottom() - grab..=strip_rect.bottom(),
+            ),
+            PanelSide::Bottom => egui::Rect::from_x_y_ranges(
+                strip_rect.x_range(),
+                strip_rect.top()..=strip_rect.top() + grab,
+            ),
+        };
+        let response = ui.interact(
+            edge_rect,
+            ui.id().with((self.state_id, "strip_edge")),
+            egui::Sense::click_and_drag(),
+        );
+        if response.double_clicked() {
+            self.cycle_size();
+        }
+        if response.hovered() || response.dragged() {
+            ui.ctx().set_cursor_icon(match self.side {
+                PanelSide::Left | PanelSide::Right => egui::CursorIcon::ResizeHorizontal,
+                PanelSide::Top | PanelSide::Bottom => egui::CursorIcon::ResizeVertical,
+            });
+        }
+        if !response.dragged() && !response.drag_stopped() {
+            return;
+        }
+        let Some(pointer) = ui.ctx().pointer_latest_pos() else {
+            return;
+        };
+
+        let panel_state = self.panel_state();
+        let extent = edge_distance(self.side, strip_rect, pointer);
+        if extent < panel_state.min_size * COLLAPSE_SNAP_RATIO {
+            return;
+        }
+        let size = extent
+            .max(panel_state.min_size)
+            .min(panel_state.max_size.unwrap_or(f32::INFINITY));
+
+        if response.drag_stopped() {
+            let old = self.get_size();
+            self.collapsible_state
+                .set_panel_size(self.panel_key(), size);
+            let new = self.get_size();
+            if new != old {
+                self.events.push(PanelEvent::Resized { old, new });
+            }
+            self.set_collapsed(false);
+            self.overlay_open = false;
+        } else {
+            // 预览展开后的面板区域
+            let painter = ui.ctx().layer_painter(egui::LayerId::new(
+                egui::Order::Foreground,
+                self.state_id.with("expand_preview"),
+            ));
+            let selection = ui.visuals().selection;
+            let rect = rect_with_extent(self.side, strip_rect, size);
+            painter.rect_filled(rect, 0.0, selection.bg_fill.gamma_multiply(0.2));
+            painter.rect_stroke(rect, 0.0, selection.stroke, egui::StrokeKind::Inside);
+        }
+    }
+
+    /// 根据动画进度和过渡方式显示面板内容
+    fn show_panel_content(
+        &mut self,
+        ui: &mut Ui,
+        tab_viewer: &mut Tab,
+        animation_value: f32,
+        full_size: f32,
+    ) {
+        if animation_value < 0.01 {
+            self.show_collapsed_content(ui, animation_value);
+            return;
+        }
+        if animation_value > 0.99 {
+            let panel_rect = ui.max_rect();
+            self.show_expanded_content(ui, tab_viewer);
+            self.show_pin_button(ui, panel_rect);
+            self.show_move_handle(ui, panel_rect);
+            return;
+        }
+
+        let visible_rect = ui.max_rect();
+        let clip_rect = visible_rect.intersect(ui.clip_rect());
+        match self.collapsible_state.transition {
+            TransitionMode::Clip => {
+                self.show_expanded_content(ui, tab_viewer);
+            }
+            TransitionMode::Slide | TransitionMode::Reveal => {
+                let anchor_inner_edge = self.collapsible_state.transition == TransitionMode::Slide;
+                let content_rect =
+                    self.full_content_rect(visible_rect, full_size, anchor_inner_edge);
+                let mut content_ui = ui.new_child(
+                    egui::UiBuilder::new()
+                        .id_salt("transition_content")
+                        .max_rect(content_rect),
+                );
+                content_ui.set_clip_rect(clip_rect);
+                self.show_expanded_content(&mut content_ui, tab_viewer);
+            }
+            TransitionMode::CrossFade => {
+                let eased = self
+                    .collapsible_state
+                    .easing
+                    .apply(animation_value)
+                    .clamp(0.0, 1.0);
+
+                let mut collapsed_ui = ui.new_child(
+                    egui::UiBuilder::new()
+                        .id_salt("transition_collapsed")
+                        .max_rect(visible_rect),
+                );
+                collapsed_ui.multiply_opacity(1.0 - eased);
+                self.show_collapsed_content(&mut collapsed_ui, animation_value);
+
+                let mut expanded_ui = ui.new_child(
+                    egui::UiBuilder::new()
+                        .id_salt("transition_expanded")
+                        .max_rect(visible_rect),
+                );
+                expanded_ui.multiply_opacity(eased);
+                self.show_expanded_content(&mut expanded_ui, tab_viewer);
+            }
+        }
+    }
+
+    /// 在面板右上角显示固定/取消固定按钮
+    fn show_pin_button(&mut self, ui: &mut Ui, panel_rect: egui::Rect) {
+        let size = Vec2::splat(18.0);
+        let rect = egui::Rect::from_min_size(
+            egui::Pos2::new(panel_rect.right() - size.x - 4.0, panel_rect.top() + 4.0),
+            size,
+        );
+        let pinned = self.is_pinned();
+        let (icon, tooltip) = if pinned {
+            (phosphor::PUSH_PIN, StringKey::UnpinPanel)
+        } else {
+            (phosphor::PUSH_PIN_SLASH, StringKey::PinPanel)
+        };
+
+        let button = egui::Button::new(icon).frame(false).min_size(size);
+        let tooltip = self.strings.get(tooltip);
+        if ui.put(rect, button).on_hover_text(tooltip).clicked() {
+            self.set_pinned(!pinned);
+        }
+    }
+
+    /// 在固定按钮左侧显示移动面板的拖动手柄
+    fn show_move_handle(&mut self, ui: &mut Ui, panel_rect: egui::Rect) {
+        let size = Vec2::splat(18.0);
+        let rect = egui::Rect::from_min_size(
+            egui::Pos2::new(
+                panel_rect.right() - 2.0 * size.x - 8.0,
+                panel_rect.top() + 4.0,
+            ),
+            size,
+        );
+        let handle = egui::Button::new(phosphor::DOTS_SIX_VERTICAL)
+            .frame(false)
+            .min_size(size)
+            .sense(egui::Sense::drag());
+        let response = ui
+            .put(rect, handle)
+            .on_hover_text(self.strings.get(StringKey::DragToMovePanel));
+        if response.hovered() || response.dragged() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
+        }
+        self.handle_move_drag(ui.ctx(), &response);
+    }
+
+    /// 拖动折叠栏或移动手柄时记录目标方向，松开后移动面板
+    fn handle_move_drag(&mut self, ctx: &Context, response: &Response) {
+        if response.drag_stopped() {
+            if let Some(side) = self.move_target.take() {
+                self.strip_action = Some(StripAction::MoveTo(side));
+            }
+            self.moving_panel = false;
+        } else if response.dragged() {
+            self.moving_panel = true;
+            self.move_target = ctx
+                .pointer_latest_pos()
+                .and_then(|pos| move_target_at(ctx.content_rect(), pos))
+                .filter(|side| *side != self.side);
+            ctx.set_cursor_icon(egui::CursorIcon::Grabbing);
+        }
+    }
+
+    /// 在窗口各边绘制移动面板的目标区域，指针所在的区域高亮显示
+    fn paint_move_targets(&self, ctx: &Context) {
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            self.state_id.with("move_targets"),
+        ));
+        let selection = ctx.style().visuals.selection;
+        let content_rect = ctx.content_rect();
+
+        for side in ALL_SIDES.into_iter().filter(|side| *side != self.side) {
+            let rect = move_target_rect(content_rect, side);
+            if self.move_target == Some(side) {
+                painter.rect_filled(rect, 0.0, selection.bg_fill.gamma_multiply(0.35));
+                painter.rect_stroke(rect, 0.0, selection.stroke, egui::StrokeKind::Inside);
+            } else {
+                painter.rect_filled(rect, 0.0, selection.bg_fill.gamma_multiply(0.1));
+            }
+        }
+    }
+
+    /// 计算完整尺寸的内容区域
+    ///
+    /// `anchor_inner_edge` 为 `true` 时贴住面板朝向中央区域的一侧（滑动），否则贴住窗口边缘（显露）。
+    fn full_content_rect(
+        &self,
+        visible_rect: egui::Rect,
+        full_size: f32,
+        anchor_inner_edge: bool,
+    ) -> egui::Rect {
+        let anchor_start =
+            matches!(self.side, PanelSide::Left | PanelSide::Top) != anchor_inner_edge;
+        match self.side {
+            PanelSide::Left | PanelSide::Right => {
+                let width = full_size.max(visible_rect.width());
+                if anchor_start {
+                    egui::Rect::from_min_size(
+                        visible_rect.min,
+                        egui::Vec2::new(width, visible_rect.height()),
+                    )
+                } else {
+                    egui::Rect::from_min_max(
+                        egui::Pos2::new(visible_rect.right() - width, visible_rect.top()),
+                        visible_rect.max,
+                    )
                 }
-
-                panel.show(ctx, |ui| {
-                    // 根据动画进度决定显示内容
-                    if animation_value < 0.3 {
-                        // 折叠状态
-                        self.show_collapsed_content(ui, animation_value);
-                    } else if animation_value > 0.7 {
-                        // 展开状态
-                        self.show_expanded_content(ui, tab_viewer);
-                    } else {
-                        // 过渡状态 - 显示加载或空白
-                        ui.centered_and_justified(|ui| {
-                            ui.spinner();
-                        });
-                    }
-                })
             }
-        };
-
-        // 保存用户调整的尺寸
-        if !is_collapsed {
-            let actual_size = match self.side {
-                PanelSide::Left | PanelSide::Right => panel_response.response.rect.width(),
-                PanelSide::Top | PanelSide::Bottom => panel_response.response.rect.height(),
-            };
-
-            // 只有当尺寸发生显著变化时才保存，避免频繁的微小调整
-            let current_saved_size = self.get_size();
-            if (actual_size - current_saved_size).abs() > 5.0 {
-                // println!("Saving panel size: side={:?}, old={}, new={}",
-                //     self.side, current_saved_size, actual_size);
-                self.collapsible_state
-                    .set_panel_size(self.side, actual_size);
+            PanelSide::Top | PanelSide::Bottom => {
+                let height = full_size.max(visible_rect.height());
+                if anchor_start {
+                    egui::Rect::from_min_size(
+                        visible_rect.min,
+                        egui::Vec2::new(visible_rect.width(), height),
+                    )
+                } else {
+                    egui::Rect::from_min_max(
+                        egui::Pos2::new(visible_rect.left(), visible_rect.bottom() - height),
+                        visible_rect.max,
+                    )
+                }
             }
         }
-
-        Some(panel_response.response)
     }
 
     /// 显示左侧面板
@@ -654,6 +2266,17 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
         let button_size = Vec2::new(icon_size + padding, icon_size + padding); // 20x20 像素的按钮
         let spacing = 2.0; // 适当的按钮间距
 
+        // 折叠栏空白处的右键菜单和拖动（移动面板），按钮的右键菜单在 `show_strip_buttons` 中处理
+        let strip_rect = ui.max_rect();
+        let strip_response = ui.interact(
+            strip_rect,
+            ui.id().with((self.state_id, "strip_menu")),
+            egui::Sense::click_and_drag(),
+        );
+        self.handle_move_drag(ui.ctx(), &strip_response);
+        // 内侧边缘在空白处之上、按钮之下
+        self.handle_strip_edge_drag(ui, strip_rect);
+
         // 根据面板方向调整布局
         match self.side {
             PanelSide::Left | PanelSide::Right => {
@@ -674,31 +2297,20 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
                             );
 
                             // 显示图标按钮
-                            let mut clicked_button = None;
-                            for (i, button) in self.buttons.iter().enumerate() {
-                                ui.push_id(i, |ui| {
-                                    // 折叠状态下，不应该有激活按钮（VS Code 风格）
-                                    let is_active =
-                                        !self.is_collapsed() && self.active_button_index == Some(i);
-                                    let response = self.show_vscode_style_button(
-                                        ui,
-                                        button,
-                                        button_size,
-                                        icon_size,
-                                        is_active,
-                                    );
-                                    if response.clicked() {
-                                        clicked_button = Some(i);
-                                    }
-                                });
-                            }
-                            if let Some(index) = clicked_button {
-                                // 展开面板并设置激活按钮
-                                self.set_collapsed(false);
-                                self.active_button_index = Some(index);
-                                // #[cfg(debug_assertions)]
-                                // println!("🎯 点击按钮 {} 展开面板，设置为激活状态", index);
-                            }
+                            let pitch = button_size.y + spacing;
+                            self.show_strip_buttons(ui, pitch, |panel, ui, i, offset| {
+                                // 折叠状态下，不应该有激活按钮（VS Code 风格），浮动面板打开时除外
+                                let is_active = (!panel.is_collapsed() || panel.overlay_open)
+                                    && panel.active_button_index == Some(i);
+                                panel.show_vscode_style_button(
+                                    ui,
+                                    &panel.buttons[i],
+                                    button_size,
+                                    icon_size,
+                                    is_active,
+                                    offset,
+                                )
+                            });
                         },
                     );
                 });
@@ -711,125 +2323,360 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
                         // 添加展开按钮
                         if ui
                             .small_button(phosphor::CARET_DOWN)
-                            .on_hover_text("展开面板")
+                            .on_hover_text(self.strings.get(StringKey::ExpandPanel))
                             .clicked()
                         {
-                            self.set_collapsed(false);
+                            self.expand_from_strip();
                         }
 
                         ui.add_space(4.0);
 
                         // 显示SVG图标按钮
-                        let mut clicked_button = None;
-                        for (i, button) in self.buttons.iter().enumerate() {
-                            ui.push_id(i, |ui| {
-                                let response = self.show_collapsed_svg_button(
-                                    ui,
-                                    button,
-                                    Vec2::splat(icon_size + 4.0), // 为水平布局使用稍小的按钮
-                                    animation_value,
-                                );
-                                if response.clicked() {
-                                    clicked_button = Some(i);
-                                }
-                            });
-                        }
-                        if let Some(index) = clicked_button {
-                            // 展开面板并设置激活按钮
-                            self.set_collapsed(false);
-                            self.active_button_index = Some(index);
-                            // #[cfg(debug_assertions)]
-                            // println!("🎯 水平布局：点击按钮 {} 展开面板，设置为激活状态", index);
-                        }
+                        let pitch = icon_size + spacing;
+                        self.show_strip_buttons(ui, pitch, |panel, ui, i, offset| {
+                            panel.show_collapsed_svg_button(
+                                ui,
+                                &panel.buttons[i],
+                                Vec2::splat(icon_size + 4.0), // 为水平布局使用稍小的按钮
+                                animation_value,
+                                offset,
+                            )
+                        });
                     });
                 });
             }
         }
+
+        strip_response.context_menu(|ui| self.show_strip_menu(ui, None));
     }
 
-    /// 显示折叠按钮
-    fn show_collapsed_button(
-        &self,
+    /// 按显示顺序绘制折叠栏按钮，处理点击、悬停和拖动排序
+    ///
+    /// `pitch` 是相邻按钮之间的距离；`show_button` 在按钮区域偏移 `offset` 的位置绘制按钮。
+    fn show_strip_buttons(
+        &mut self,
         ui: &mut Ui,
-        button: &CollapsibleButton,
-        size: Vec2,
-        animation_value: f32,
-    ) -> Response {
-        let button_text = if let Some(ref icon) = button.icon {
-            if animation_value > 0.5 {
-                format!("{} {}", icon, button.text)
-            } else {
-                icon.clone()
-            }
-        } else {
-            if animation_value > 0.5 {
-                button.text.clone()
-            } else {
-                button.text.chars().next().unwrap_or('?').to_string()
-            }
+        pitch: f32,
+        mut show_button: impl FnMut(&Self, &mut Ui, usize, Vec2) -> Response,
+    ) {
+        let order = self.visible_button_order();
+        let axis = match self.side {
+            PanelSide::Left | PanelSide::Right => Vec2::Y,
+            PanelSide::Top | PanelSide::Bottom => Vec2::X,
         };
 
-        let mut button_ui = egui::Button::new(button_text).min_size(size);
+        let mut clicked_button = None;
+        let mut hovered_button = None;
+        let mut drag_update = None;
+        let mut responses = Vec::with_capacity(order.len());
+        for (slot, &index) in order.iter().enumerate() {
+            // 用按钮索引作为 ID，排序后按钮的交互状态保持不变
+            ui.push_id(index, |ui| {
+                let visual_slot = self.preview_slot(ui.ctx(), index, slot, order.len(), pitch);
+                let offset = axis * (visual_slot - slot as f32) * pitch;
+                let response = show_button(self, ui, index, offset);
+                if response.drag_stopped() {
+                    drag_update = Some(None);
+                } else if response.dragged() {
+                    // 从按下位置计算拖动距离，包含拖动判定阈值内的移动
+                    let offset = ui.input(|input| {
+                        let origin = input.pointer.press_origin();
+                        origin
+                            .zip(input.pointer.interact_pos())
+                            .map_or(0.0, |(origin, pos)| (pos - origin).dot(axis))
+                    });
+                    drag_update = Some(Some(ButtonDrag { slot, offset }));
+                }
+                if response.clicked() {
+                    clicked_button = Some(index);
+                } else if response.hovered() {
+                    hovered_button = Some(index);
+                }
+                responses.push((index, response));
+            });
+        }
+
+        for (index, response) in responses {
+            response.context_menu(|ui| self.show_strip_menu(ui, Some(index)));
+        }
 
-        if button.selected {
-            button_ui = button_ui.selected(true);
+        if let Some(drag) = drag_update {
+            if let (None, Some(finished)) = (drag, self.button_drag) {
+                self.move_button(
+                    &order,
+                    finished.slot,
+                    finished.target_slot(pitch, order.len()),
+                );
+            }
+            self.button_drag = drag;
         }
 
-        let response = ui.add(button_ui);
+        if let Some(index) = clicked_button {
+            // 展开面板并设置激活按钮
+            self.strip_button_clicked(index);
+        } else if let Some(index) = hovered_button.filter(|_| self.button_drag.is_none()) {
+            self.strip_button_hovered(index);
+        }
+    }
 
-        // 添加工具提示
-        let response = if let Some(ref tooltip) = button.tooltip {
-            response.on_hover_text(tooltip)
+    /// 按钮绘制位置（以按钮间距为单位）：拖动时其余按钮以动画方式让出位置
+    fn preview_slot(
+        &self,
+        ctx: &Context,
+        index: usize,
+        slot: usize,
+        count: usize,
+        pitch: f32,
+    ) -> f32 {
+        let id = self.state_id.with(("button_slot", index));
+        let Some(drag) = self.button_drag else {
+            return animate_to(ctx, id, slot as f32, BUTTON_REORDER_DURATION);
+        };
+        if slot == drag.slot {
+            // 被拖动的按钮跟随指针
+            return animate_to(ctx, id, drag.visual_slot(pitch, count), 0.0);
+        }
+
+        let target = drag.target_slot(pitch, count);
+        let preview = if drag.slot < slot && slot <= target {
+            slot - 1
+        } else if target <= slot && slot < drag.slot {
+            slot + 1
         } else {
-            response.on_hover_text(&button.text)
+            slot
         };
+        animate_to(ctx, id, preview as f32, BUTTON_REORDER_DURATION)
+    }
 
-        response
+    /// 将可见按钮中位置 `from` 的按钮移动到 `to`，并保存新的顺序
+    fn move_button(&mut self, visible: &[usize], from: usize, to: usize) {
+        if from == to {
+            return;
+        }
+        let mut visible = visible.to_vec();
+        let index = visible.remove(from);
+        visible.insert(to, index);
+
+        // 隐藏的按钮保持原来的位置，可见按钮按新顺序依次填入
+        let mut reordered = visible.into_iter();
+        let ids: Vec<String> = self
+            .button_display_order()
+            .into_iter()
+            .map(|index| {
+                let index = if self.is_index_hidden(index) {
+                    index
+                } else {
+                    reordered.next().unwrap_or(index)
+                };
+                self.buttons[index].id.clone()
+            })
+            .collect();
+        self.set_button_order(ids);
+        self.events.push(PanelEvent::ButtonsReordered);
     }
 
-    /// 显示折叠状态下的Phosphor图标按钮
-    fn show_collapsed_svg_button(
-        &self,
-        ui: &mut Ui,
-        button: &CollapsibleButton,
-        _size: Vec2,
-        _animation_value: f32,
-    ) -> Response {
-        // 检查是否有 SVG 图标标识符
-        if let Some(ref icon_str) = button.icon {
-            if icon_str.starts_with("svg:") {
-                // 这是一个 SVG 图标，使用自定义渲染
-                return self.render_custom_svg_button(ui, button, Vec2::splat(14.0));
-                // VSCode style small icon
+    /// 按钮的显示顺序（按钮索引）：先按保存的 ID 顺序，之后是新增的按钮
+    fn button_display_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = Vec::with_capacity(self.buttons.len());
+        for id in &self.panel_state().button_order {
+            let index = self.buttons.iter().position(|button| &button.id == id);
+            if let Some(index) = index.filter(|index| !order.contains(index)) {
+                order.push(index);
+            }
+        }
+        for index in 0..self.buttons.len() {
+            if !order.contains(&index) {
+                order.push(index);
             }
         }
+        order
+    }
 
-        // 使用Phosphor图标（原有逻辑）
-        let icon = match button.text.as_str() {
-            "Search" => phosphor::MAGNIFYING_GLASS,         // 搜索图标
-            "Files" => phosphor::FOLDER,                    // 文件夹图标
-            "Diagnostics" => phosphor::WARNING,             // 警告图标
-            "History" => phosphor::CLOCK_COUNTER_CLOCKWISE, // 历史图标
-            "Settings" => phosphor::GEAR,                   // 设置图标
-            "场景树" => phosphor::TREE_STRUCTURE,           // 场景树图标
-            "属性" => phosphor::LIST_BULLETS,               // 属性图标
-            "控制台" => phosphor::TERMINAL,                 // 控制台图标
-            _ => phosphor::CIRCLE,                          // 默认圆点
-        };
+    /// 折叠栏中显示的按钮（按显示顺序，不含隐藏的按钮）
+    fn visible_button_order(&self) -> Vec<usize> {
+        let mut order = self.button_display_order();
+        order.retain(|&index| !self.is_index_hidden(index));
+        order
+    }
+
+    /// 指定索引的按钮是否被隐藏
+    fn is_index_hidden(&self, index: usize) -> bool {
+        self.is_button_hidden(&self.buttons[index].id)
+    }
 
-        let button_ui = egui::Button::new(icon).min_size(Vec2::splat(14.0)); // VSCode style small icon
-                                                                             // VSCode style: no selection state for collapsed buttons
+    /// 显示折叠栏右键菜单，`button` 为被右键点击的按钮
+    fn show_strip_menu(&mut self, ui: &mut Ui, button: Option<usize>) {
+        let mut action = None;
 
-        let response = ui.add(button_ui);
+        if let Some(index) = button {
+            let label = self
+                .strings
+                .format(StringKey::HideButton, &self.buttons[index].text);
+            if ui.button(label).clicked() {
+                action = Some(StripAction::HideButton(index));
+            }
+        }
 
-        // 添加工具提示
-        let response = if let Some(ref tooltip) = button.tooltip {
-            response.on_hover_text(tooltip)
+        let hidden: Vec<usize> = (0..self.buttons.len())
+            .filter(|&index| self.is_index_hidden(index))
+            .collect();
+        ui.add_enabled_ui(!hidden.is_empty(), |ui| {
+            ui.menu_button(self.strings.get(StringKey::ShowHiddenButtons), |ui| {
+                for &index in &hidden {
+                    if ui.button(&self.buttons[index].text).clicked() {
+                        action = Some(StripAction::ShowButton(index));
+                    }
+                }
+                ui.separator();
+                if ui
+                    .button(self.strings.get(StringKey::ShowAllButtons))
+                    .clicked()
+                {
+                    action = Some(StripAction::ShowAllButtons);
+                }
+            });
+        });
+
+        ui.menu_button(self.strings.get(StringKey::MoveTo), |ui| {
+            let sides = [
+                (PanelSide::Left, StringKey::SideLeft),
+                (PanelSide::Right, StringKey::SideRight),
+                (PanelSide::Top, StringKey::SideTop),
+                (PanelSide::Bottom, StringKey::SideBottom),
+            ];
+            for (side, label) in sides {
+                if side != self.side && ui.button(self.strings.get(label)).clicked() {
+                    action = Some(StripAction::MoveTo(side));
+                }
+            }
+        });
+
+        if ui.button(self.strings.get(StringKey::ResetSize)).clicked() {
+            action = Some(StripAction::ResetSize);
+        }
+        let toggle_label = if self.is_collapsed() {
+            StringKey::ExpandPanel
         } else {
-            response.on_hover_text(&button.text)
+            StringKey::CollapsePanel
         };
+        if ui.button(self.strings.get(toggle_label)).clicked() {
+            action = Some(StripAction::Toggle);
+        }
 
-        response
+        let button_id = button.map(|index| self.buttons[index].id.clone());
+        if let Some(menu) = &mut self.strip_menu {
+            ui.separator();
+            menu(ui, button_id.as_deref());
+        }
+
+        if action.is_some() {
+            self.strip_action = action;
+            ui.close();
+        }
+    }
+
+    /// 执行右键菜单选择的操作
+    fn run_strip_action(&mut self, action: StripAction) {
+        match action {
+            StripAction::HideButton(index) => {
+                let id = self.buttons[index].id.clone();
+                self.set_button_hidden(&id, true);
+            }
+            StripAction::ShowButton(index) => {
+                let id = self.buttons[index].id.clone();
+                self.set_button_hidden(&id, false);
+            }
+            StripAction::ShowAllButtons => self.unhide_all_buttons(),
+            StripAction::MoveTo(side) => self.set_side(side),
+            StripAction::ResetSize => self.reset_size(),
+            StripAction::Toggle => self.toggle(),
+        }
+    }
+
+    /// 折叠栏按钮被点击：停靠模式下展开面板，浮动模式下打开（或关闭）浮动面板
+    fn strip_button_clicked(&mut self, index: usize) {
+        if self.expand_mode == ExpandMode::Docked || !self.is_collapsed() {
+            self.activate_button(index);
+        } else if self.overlay_open && self.active_button_index == Some(index) {
+            self.set_overlay_open(false);
+        } else {
+            self.open_overlay_for(index);
+        }
+    }
+
+    /// 折叠栏按钮被悬停：悬停浮动模式下打开对应的浮动面板
+    fn strip_button_hovered(&mut self, index: usize) {
+        let opens_on_hover = self.expand_mode == ExpandMode::OverlayOnHover && self.is_collapsed();
+        if opens_on_hover && !(self.overlay_open && self.active_button_index == Some(index)) {
+            self.open_overlay_for(index);
+        }
+    }
+
+    /// 折叠栏展开按钮被点击
+    fn expand_from_strip(&mut self) {
+        if self.expand_mode == ExpandMode::Docked {
+            self.set_collapsed(false);
+        } else {
+            self.set_overlay_open(true);
+        }
+    }
+
+    /// 打开浮动面板并激活按钮
+    fn open_overlay_for(&mut self, index: usize) {
+        self.active_button_index = Some(index);
+        self.focus_bound_tab(index);
+        self.events.push(PanelEvent::ButtonActivated(index));
+        self.set_overlay_open(true);
+    }
+
+    /// 激活按钮：展开面板、聚焦绑定的标签页并记录事件
+    fn activate_button(&mut self, index: usize) {
+        self.set_collapsed(false);
+        self.active_button_index = Some(index);
+        self.focus_bound_tab(index);
+        self.events.push(PanelEvent::ButtonActivated(index));
+    }
+
+    /// 将按钮绑定的标签页设为活动标签页并聚焦其所在区域
+    fn focus_bound_tab(&mut self, index: usize) {
+        if let Some(Some(predicate)) = self.button_bindings.get(index) {
+            if let Some(path) = self.dock_state.find_tab_from(|tab| predicate(tab)) {
+                let (surface, node, _) = path;
+                self.dock_state
+                    .set_focused_node_and_surface((surface, node));
+                self.dock_state.set_active_tab(path);
+            }
+        }
+    }
+
+    /// 根据 Dock 中获得焦点的标签页同步活动按钮
+    fn sync_active_button_from_dock(&mut self) {
+        let bindings = &self.button_bindings;
+        let focused_button = self.dock_state.find_active_focused().and_then(|(_, tab)| {
+            bindings
+                .iter()
+                .position(|binding| binding.as_ref().is_some_and(|p| p(&*tab)))
+        });
+
+        // 只在焦点标签页变化时更新，避免覆盖按钮点击
+        if focused_button != self.last_focused_button {
+            self.last_focused_button = focused_button;
+            if focused_button.is_some() {
+                self.active_button_index = focused_button;
+            }
+        }
+    }
+
+    /// 显示折叠状态下的图标按钮
+    fn show_collapsed_svg_button(
+        &self,
+        ui: &mut Ui,
+        button: &CollapsibleButton,
+        _size: Vec2,
+        _animation_value: f32,
+        offset: Vec2,
+    ) -> Response {
+        self.render_custom_svg_button(ui, button, Vec2::splat(14.0), offset) // VSCode style small icon
     }
 
     /// 显示 VS Code 风格的按钮
@@ -840,8 +2687,11 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
         size: Vec2,
         icon_size: f32,
         is_active: bool,
+        offset: Vec2,
     ) -> Response {
-        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click_and_drag());
+        // 拖动排序时按钮绘制在偏移后的位置
+        let rect = rect.translate(offset);
 
         if ui.is_rect_visible(rect) {
             let painter = ui.painter();
@@ -890,21 +2740,9 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
             let icon_rect =
                 egui::Rect::from_center_size(rect.center(), egui::Vec2::splat(icon_size));
 
-            // 检查是否有 SVG 图标
-            if let Some(ref icon_str) = button.icon {
-                if icon_str.starts_with("svg:") {
-                    let icon_name = &icon_str[4..];
-                    // 调试信息：打印图标名称
-                    // #[cfg(debug_assertions)]
-                    // println!("🎨 绘制 SVG 图标: {} (来自: {})", icon_name, icon_str);
-                    self.draw_custom_svg_icon(ui, icon_name, icon_rect, icon_color);
-                } else {
-                    // 根据按钮类型绘制不同的图标
-                    self.draw_button_icon(painter, &button.text, icon_rect, icon_color, icon_size);
-                }
-            } else {
-                // 根据按钮类型绘制不同的图标
-                self.draw_button_icon(painter, &button.text, icon_rect, icon_color, icon_size);
+            self.paint_button_icon(painter, button, icon_rect, icon_color);
+            if let Some(badge) = &button.badge {
+                paint_badge(painter, rect, badge, &ui.style().visuals);
             }
         }
 
@@ -916,107 +2754,17 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
         }
     }
 
-    /// 绘制按钮图标
-    fn draw_button_icon(
-        &self,
-        painter: &egui::Painter,
-        button_text: &str,
-        rect: egui::Rect,
-        color: egui::Color32,
-        icon_size: f32,
-    ) {
-        let stroke = egui::Stroke::new(1.5, color);
-
-        match button_text {
-            "场景树" => {
-                // 绘制树形结构图标
-                let x = rect.left() + rect.width() * 0.2;
-                let y_start = rect.top() + rect.height() * 0.2;
-                let y_end = rect.bottom() - rect.height() * 0.2;
-
-                // 主干
-                painter.line_segment(
-                    [egui::Pos2::new(x, y_start), egui::Pos2::new(x, y_end)],
-                    stroke,
-                );
-
-                // 分支
-                for i in 0..3 {
-                    let y = y_start + (y_end - y_start) * (i as f32 + 0.5) / 3.0;
-                    let x_end = rect.right() - rect.width() * 0.2;
-                    painter.line_segment(
-                        [
-                            egui::Pos2::new(x, y),
-                            egui::Pos2::new(x + rect.width() * 0.2, y),
-                        ],
-                        stroke,
-                    );
-                    painter.circle_filled(egui::Pos2::new(x_end, y), 2.0, color);
-                }
-            }
-            "属性" => {
-                // 绘制列表图标
-                for i in 0..3 {
-                    let y = rect.top() + rect.height() * (i as f32 + 1.0) / 4.0;
-                    let line_rect = egui::Rect::from_min_size(
-                        egui::Pos2::new(rect.left() + rect.width() * 0.2, y - 1.0),
-                        egui::Vec2::new(rect.width() * 0.6, 2.0),
-                    );
-                    painter.rect_filled(line_rect, 1.0, color);
-                }
-            }
-            "控制台" => {
-                // 绘制终端图标
-                let terminal_rect = rect.shrink(rect.width() * 0.15);
-                painter.rect_stroke(terminal_rect, 2.0, stroke, egui::StrokeKind::Outside);
-
-                // 提示符
-                let prompt_pos = terminal_rect.min
-                    + egui::Vec2::new(terminal_rect.width() * 0.1, terminal_rect.height() * 0.6);
-                painter.text(
-                    prompt_pos,
-                    egui::Align2::LEFT_CENTER,
-                    ">_",
-                    egui::FontId::proportional(icon_size * 0.7),
-                    color,
-                );
-            }
-            _ => {
-                // 默认图标
-                painter.circle_filled(rect.center(), rect.width() * 0.3, color);
-            }
-        }
-    }
-
-    /// 渲染自定义 SVG 按钮
+    /// 渲染注册表图标按钮
     fn render_custom_svg_button(
         &self,
         ui: &mut Ui,
         button: &CollapsibleButton,
         size: Vec2,
+        offset: Vec2,
     ) -> Response {
-        // 解析图标 ID
-        let icon_id = if let Some(ref icon_str) = button.icon {
-            if icon_str.starts_with("svg:") {
-                let icon_name = &icon_str[4..];
-                match icon_name {
-                    "SceneTree" => Some("SceneTree"),
-                    "Properties" => Some("Properties"),
-                    "Console" => Some("Console"),
-                    "Files" => Some("Files"),
-                    "Terminal" => Some("Terminal"),
-                    "Settings" => Some("Settings"),
-                    _ => None,
-                }
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-
         // 分配按钮区域
-        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click_and_drag());
+        let rect = rect.translate(offset);
 
         if ui.is_rect_visible(rect) {
             let visuals = ui.style().interact(&response);
@@ -1037,19 +2785,17 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
             }
 
             // 绘制图标
-            if let Some(icon_name) = icon_id {
-                self.draw_custom_svg_icon(ui, icon_name, rect, text_color);
-            } else {
-                // 默认图标
-                ui.painter()
-                    .circle_filled(rect.center(), size.x * 0.3, text_color);
-            }
+            self.paint_button_icon(ui.painter(), button, rect, text_color);
 
             // 绘制边框
             if response.hovered() || button.selected {
                 ui.painter()
                     .rect_stroke(rect, corner_radius, bg_stroke, egui::StrokeKind::Outside);
             }
+
+            if let Some(badge) = &button.badge {
+                paint_badge(ui.painter(), rect, badge, &ui.style().visuals);
+            }
         }
 
         // 添加工具提示
@@ -1060,290 +2806,30 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
         }
     }
 
-    /// 绘制自定义 SVG 图标
-    fn draw_custom_svg_icon(
-        &self,
-        ui: &mut Ui,
-        icon_name: &str,
-        rect: egui::Rect,
-        color: egui::Color32,
-    ) {
-        let painter = ui.painter();
-        let center = rect.center();
-        let icon_size = rect.size() * 0.8; // 稍微缩小以留出边距
-        let icon_rect = egui::Rect::from_center_size(center, icon_size);
-
-        // #[cfg(debug_assertions)]
-        // println!("🎨 匹配图标名称: '{}', 可用选项: SceneTree, Properties, Console, Files, Terminal, Settings, Close", icon_name);
-
-        match icon_name {
-            "SceneTree" => {
-                // #[cfg(debug_assertions)]
-                // println!("✅ 绘制场景树图标");
-                self.draw_scene_tree_icon(painter, icon_rect, color);
-            }
-            "Properties" => {
-                // #[cfg(debug_assertions)]
-                // println!("✅ 绘制属性图标");
-                self.draw_properties_icon(painter, icon_rect, color);
-            }
-            "Console" => {
-                // #[cfg(debug_assertions)]
-                // println!("✅ 绘制控制台图标");
-                self.draw_console_icon(painter, icon_rect, color);
-            }
-            "Files" => {
-                // #[cfg(debug_assertions)]
-                // println!("✅ 绘制文件管理器图标");
-                self.draw_files_icon(painter, icon_rect, color);
-            }
-            "Terminal" => {
-                // #[cfg(debug_assertions)]
-                // println!("✅ 绘制终端图标");
-                self.draw_terminal_icon(painter, icon_rect, color);
-            }
-            "Settings" => {
-                // #[cfg(debug_assertions)]
-                // println!("✅ 绘制设置图标");
-                self.draw_settings_icon(painter, icon_rect, color);
-            }
-            "Close" => {
-                // #[cfg(debug_assertions)]
-                // println!("✅ 绘制关闭图标");
-                self.draw_close_icon(painter, icon_rect, color);
-            }
-            _ => {
-                // #[cfg(debug_assertions)]
-                // println!("❌ 未知图标名称: '{}', 使用默认圆点", icon_name);
-                // 默认图标
-                painter.circle_filled(center, icon_size.x * 0.3, color);
-            }
-        }
-    }
-
-    /// 绘制场景树图标
-    fn draw_scene_tree_icon(
-        &self,
-        painter: &egui::Painter,
-        rect: egui::Rect,
-        color: egui::Color32,
-    ) {
-        let stroke = egui::Stroke::new(1.5, color);
-        let line_height = rect.height() / 6.0;
-        let indent = rect.width() * 0.15;
-
-        // 垂直连接线
-        let x = rect.left() + indent;
-        painter.line_segment(
-            [
-                egui::Pos2::new(x, rect.top() + line_height),
-                egui::Pos2::new(x, rect.bottom() - line_height),
-            ],
-            stroke,
-        );
-
-        // 水平线和节点
-        for i in 0..3 {
-            let y = rect.top() + line_height * (2.0 + i as f32 * 2.0);
-            let node_x = x + indent;
-
-            // 水平连接线
-            painter.line_segment([egui::Pos2::new(x, y), egui::Pos2::new(node_x, y)], stroke);
-
-            // 节点矩形
-            let node_rect = egui::Rect::from_min_size(
-                egui::Pos2::new(node_x, y - line_height * 0.3),
-                egui::Vec2::new(rect.width() - indent * 2.5, line_height * 0.6),
-            );
-            painter.rect_filled(node_rect, 2.0, color);
-        }
-    }
-
-    /// 绘制属性图标
-    fn draw_properties_icon(
+    /// 通过图标注册表绘制按钮图标
+    fn paint_button_icon(
         &self,
         painter: &egui::Painter,
+        button: &CollapsibleButton,
         rect: egui::Rect,
         color: egui::Color32,
     ) {
-        let stroke = egui::Stroke::new(1.5, color);
-        let margin = rect.width() * 0.1;
-        let content_rect = rect.shrink(margin);
-
-        // 外框
-        painter.rect_stroke(content_rect, 3.0, stroke, egui::StrokeKind::Outside);
-
-        // 内容线条
-        let line_height = content_rect.height() / 6.0;
-        for i in 0..3 {
-            let y = content_rect.top() + line_height * (1.5 + i as f32 * 1.5);
-            let line_width = content_rect.width() * (0.8 - i as f32 * 0.1);
-            let line_rect = egui::Rect::from_min_size(
-                egui::Pos2::new(content_rect.left() + margin, y - 1.0),
-                egui::Vec2::new(line_width, 2.0),
-            );
-            painter.rect_filled(line_rect, 1.0, color);
+        if self.icons.paint(painter, button.icon_key(), rect, color) {
+            return;
         }
-    }
-
-    /// 绘制控制台图标
-    fn draw_console_icon(&self, painter: &egui::Painter, rect: egui::Rect, color: egui::Color32) {
-        let stroke = egui::Stroke::new(1.5, color);
-        let margin = rect.width() * 0.05;
-        let console_rect = rect.shrink(margin);
-
-        // 控制台外框
-        painter.rect_stroke(console_rect, 3.0, stroke, egui::StrokeKind::Outside);
-
-        // 命令提示符 ">"
-        let prompt_size = console_rect.width() * 0.15;
-        let prompt_center =
-            egui::Pos2::new(console_rect.left() + prompt_size, console_rect.center().y);
-
-        // 绘制三角形提示符
-        let triangle_points = [
-            egui::Pos2::new(
-                prompt_center.x - prompt_size * 0.3,
-                prompt_center.y - prompt_size * 0.3,
-            ),
-            egui::Pos2::new(prompt_center.x + prompt_size * 0.3, prompt_center.y),
-            egui::Pos2::new(
-                prompt_center.x - prompt_size * 0.3,
-                prompt_center.y + prompt_size * 0.3,
-            ),
-        ];
-        painter.add(egui::Shape::convex_polygon(
-            triangle_points.to_vec(),
-            color,
-            egui::Stroke::NONE,
-        ));
 
-        // 命令行
-        let line_rect = egui::Rect::from_min_size(
-            egui::Pos2::new(prompt_center.x + prompt_size, prompt_center.y - 1.0),
-            egui::Vec2::new(console_rect.width() * 0.5, 2.0),
-        );
-        painter.rect_filled(line_rect, 1.0, color);
-    }
-
-    /// 绘制文件管理器图标 (Files)
-    fn draw_files_icon(&self, painter: &egui::Painter, rect: egui::Rect, color: egui::Color32) {
-        let stroke = egui::Stroke::new(1.5, color);
-
-        // 后面的文件夹
-        let back_folder = egui::Rect::from_min_size(
-            rect.min + egui::Vec2::new(rect.width() * 0.1, rect.height() * 0.3),
-            egui::Vec2::new(rect.width() * 0.6, rect.height() * 0.5),
-        );
-        painter.rect_stroke(back_folder, 2.0, stroke, egui::StrokeKind::Outside);
-
-        // 前面的文件夹
-        let front_folder = egui::Rect::from_min_size(
-            rect.min + egui::Vec2::new(rect.width() * 0.3, rect.height() * 0.15),
-            egui::Vec2::new(rect.width() * 0.6, rect.height() * 0.5),
-        );
-        painter.rect_filled(front_folder, 2.0, color.gamma_multiply(0.1));
-        painter.rect_stroke(front_folder, 2.0, stroke, egui::StrokeKind::Outside);
-
-        // 文件夹标签
-        let tab_rect = egui::Rect::from_min_size(
-            front_folder.min - egui::Vec2::new(0.0, rect.height() * 0.08),
-            egui::Vec2::new(rect.width() * 0.25, rect.height() * 0.08),
-        );
-        painter.rect_filled(tab_rect, 1.0, color.gamma_multiply(0.15));
-    }
-
-    /// 绘制终端图标 (Terminal)
-    fn draw_terminal_icon(&self, painter: &egui::Painter, rect: egui::Rect, color: egui::Color32) {
-        let stroke = egui::Stroke::new(1.5, color);
-
-        // 终端窗口边框
-        let terminal_rect = rect.shrink(rect.width() * 0.1);
-        painter.rect_stroke(terminal_rect, 3.0, stroke, egui::StrokeKind::Outside);
-
-        // 命令提示符 ">"
-        let prompt_center = egui::Pos2::new(
-            terminal_rect.left() + terminal_rect.width() * 0.2,
-            terminal_rect.center().y,
-        );
-
-        // 绘制 ">" 符号
-        painter.line_segment(
-            [
-                egui::Pos2::new(prompt_center.x - 5.0, prompt_center.y - 5.0),
-                egui::Pos2::new(prompt_center.x, prompt_center.y),
-            ],
-            stroke,
-        );
-        painter.line_segment(
-            [
-                egui::Pos2::new(prompt_center.x - 5.0, prompt_center.y + 5.0),
-                egui::Pos2::new(prompt_center.x, prompt_center.y),
-            ],
-            stroke,
-        );
-
-        // 光标
-        let cursor_rect = egui::Rect::from_min_size(
-            egui::Pos2::new(prompt_center.x + 10.0, prompt_center.y - 1.0),
-            egui::Vec2::new(8.0, 2.0),
-        );
-        painter.rect_filled(cursor_rect, 0.0, color);
-    }
-
-    /// 绘制设置图标 (Settings)
-    fn draw_settings_icon(&self, painter: &egui::Painter, rect: egui::Rect, color: egui::Color32) {
-        let center = rect.center();
-        let radius = rect.width().min(rect.height()) * 0.35;
-
-        // 绘制齿轮形状（简化版）
-        // 中心圆
-        painter.circle_filled(center, radius * 0.4, color.gamma_multiply(0.1));
-        painter.circle_stroke(center, radius * 0.4, egui::Stroke::new(1.5, color));
-
-        // 齿轮齿
-        let teeth_count = 8;
-        for i in 0..teeth_count {
-            let angle = (i as f32) * std::f32::consts::TAU / (teeth_count as f32);
-            let tooth_inner = center + egui::Vec2::angled(angle) * (radius * 0.5);
-            let tooth_outer = center + egui::Vec2::angled(angle) * radius;
-
-            painter.line_segment([tooth_inner, tooth_outer], egui::Stroke::new(2.0, color));
+        match button.icon.as_deref() {
+            // 未注册的普通图标文本（如 emoji）直接作为字形绘制
+            Some(icon) if !icon.starts_with("svg:") => paint_glyph(painter, icon, rect, color),
+            _ => paint_fallback(painter, rect, color),
         }
     }
 
-    /// 绘制关闭图标 (Close)
-    fn draw_close_icon(&self, painter: &egui::Painter, rect: egui::Rect, color: egui::Color32) {
-        let stroke = egui::Stroke::new(2.0, color);
-        let center = rect.center();
-        let size = rect.width().min(rect.height()) * 0.4;
-
-        // 绘制 X 符号的两条对角线
-        let half_size = size * 0.5;
-
-        // 左上到右下的对角线
-        painter.line_segment(
-            [
-                egui::Pos2::new(center.x - half_size, center.y - half_size),
-                egui::Pos2::new(center.x + half_size, center.y + half_size),
-            ],
-            stroke,
-        );
-
-        // 右上到左下的对角线
-        painter.line_segment(
-            [
-                egui::Pos2::new(center.x + half_size, center.y - half_size),
-                egui::Pos2::new(center.x - half_size, center.y + half_size),
-            ],
-            stroke,
-        );
-    }
-
     /// 显示展开状态下的内容
     fn show_expanded_content(&mut self, ui: &mut Ui, tab_viewer: &mut Tab) {
         // 去掉上方的最小化按钮，直接显示 dock 内容
         // 显示 dock 内容，使用唯一的 ID
+        let mut tracker = TabButtonTracker::new(tab_viewer);
         ui.push_id((self.state_id, "dock_area"), |ui| {
             egui_dock::DockArea::new(&mut self.dock_state)
                 .id(egui::Id::new((self.state_id, "dock_area_unique")))
@@ -1351,7 +2837,11 @@ impl<Tab: TabViewer> CollapsibleDockPanel<Tab> {
                 .show_leaf_collapse_buttons(false) // 直接禁用 collapse 按钮
                 .show_close_buttons(true) // 启用关闭按钮，但功能改为最小化面板
                 .show_add_buttons(false) // 禁用添加按钮
-                .show_inside(ui, tab_viewer);
+                .show_inside(ui, &mut tracker);
         });
+        self.pressed_tab
+            .update(ui.ctx(), &self.dock_state, tracker.pressed);
+
+        self.sync_active_button_from_dock();
     }
 }
@@ -0,0 +1,472 @@
+use egui::{Color32, Painter, Rect, TextureId};
+use egui_phosphor::regular as phosphor;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[cfg(feature = "svg")]
+use crate::svg_icon::{SvgIcon, SvgIconError};
+
+/// 图标绘制函数：在给定区域内用给定颜色绘制图标
+pub type IconPainter = Arc<dyn Fn(&Painter, Rect, Color32) + Send + Sync>;
+
+/// 图标来源
+#[derive(Clone)]
+pub enum Icon {
+    /// 自定义绘制函数
+    Painter(IconPainter),
+    /// 字体字形（如 Phosphor 图标或 emoji）
+    Glyph(String),
+    /// 纹理，绘制时使用图标颜色着色
+    Texture(TextureId),
+    /// SVG 图标，按尺寸光栅化并缓存
+    #[cfg(feature = "svg")]
+    Svg(Arc<SvgIcon>),
+}
+
+impl std::fmt::Debug for Icon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Painter(_) => f.write_str("Icon::Painter(..)"),
+            Self::Glyph(glyph) => f.debug_tuple("Icon::Glyph").field(glyph).finish(),
+            Self::Texture(id) => f.debug_tuple("Icon::Texture").field(id).finish(),
+            #[cfg(feature = "svg")]
+            Self::Svg(svg) => f.debug_tuple("Icon::Svg").field(svg).finish(),
+        }
+    }
+}
+
+impl Icon {
+    /// 在指定区域绘制图标
+    pub fn paint(&self, painter: &Painter, rect: Rect, color: Color32) {
+        match self {
+            Self::Painter(paint) => paint(painter, rect, color),
+            Self::Glyph(glyph) => paint_glyph(painter, glyph, rect, color),
+            Self::Texture(texture_id) => {
+                let uv = Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+                painter.image(*texture_id, rect, uv, color);
+            }
+            #[cfg(feature = "svg")]
+            Self::Svg(svg) => svg.paint(painter, rect, color),
+        }
+    }
+}
+
+/// 绘制字形图标
+pub fn paint_glyph(painter: &Painter, glyph: &str, rect: Rect, color: Color32) {
+    painter.text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        glyph,
+        egui::FontId::proportional(rect.height()),
+        color,
+    );
+}
+
+/// 未找到图标时的默认圆点
+pub fn paint_fallback(painter: &Painter, rect: Rect, color: Color32) {
+    painter.circle_filled(rect.center(), rect.width() * 0.3, color);
+}
+
+/// 图标注册表，按图标键查找图标
+///
+/// `CollapsibleButton` 的 `icon` 或按钮文本即为图标键。`svg:` 前缀的键（如 `svg:Files`）
+/// 与按钮文本键（如 `Files`）互不影响。
+#[derive(Debug, Clone, Default)]
+pub struct IconRegistry {
+    icons: HashMap<String, Icon>,
+}
+
+impl IconRegistry {
+    /// 创建空的图标注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建包含内置图标的注册表
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+
+        // 内置绘制图标，通过 `with_icon("svg:...")` 使用
+        registry.register_painter("svg:SceneTree", |p, r, c| {
+            draw_scene_tree_icon(p, inset(r), c)
+        });
+        registry.register_painter("svg:Properties", |p, r, c| {
+            draw_properties_icon(p, inset(r), c)
+        });
+        registry.register_painter("svg:Console", |p, r, c| draw_console_icon(p, inset(r), c));
+        registry.register_painter("svg:Files", |p, r, c| draw_files_icon(p, inset(r), c));
+        registry.register_painter("svg:Terminal", |p, r, c| draw_terminal_icon(p, inset(r), c));
+        registry.register_painter("svg:Settings", |p, r, c| draw_settings_icon(p, inset(r), c));
+        registry.register_painter("svg:Close", |p, r, c| draw_close_icon(p, inset(r), c));
+
+        // 按按钮文本匹配的图标
+        registry.register_painter("场景树", draw_tree_icon);
+        registry.register_painter("属性", draw_list_icon);
+        registry.register_painter("控制台", draw_prompt_icon);
+        registry.register_glyph("Search", phosphor::MAGNIFYING_GLASS);
+        registry.register_glyph("Files", phosphor::FOLDER);
+        registry.register_glyph("Diagnostics", phosphor::WARNING);
+        registry.register_glyph("History", phosphor::CLOCK_COUNTER_CLOCKWISE);
+        registry.register_glyph("Settings", phosphor::GEAR);
+
+        registry
+    }
+
+    /// 注册图标，覆盖同名图标
+    pub fn register(&mut self, key: impl Into<String>, icon: Icon) -> &mut Self {
+        self.icons.insert(key.into(), icon);
+        self
+    }
+
+    /// 注册绘制函数图标
+    pub fn register_painter(
+        &mut self,
+        key: impl Into<String>,
+        paint: impl Fn(&Painter, Rect, Color32) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.register(key, Icon::Painter(Arc::new(paint)))
+    }
+
+    /// 注册字形图标
+    pub fn register_glyph(
+        &mut self,
+        key: impl Into<String>,
+        glyph: impl Into<String>,
+    ) -> &mut Self {
+        self.register(key, Icon::Glyph(glyph.into()))
+    }
+
+    /// 注册纹理图标
+    pub fn register_texture(&mut self, key: impl Into<String>, texture_id: TextureId) -> &mut Self {
+        self.register(key, Icon::Texture(texture_id))
+    }
+
+    /// 注册已创建的 SVG 图标
+    ///
+    /// 需要保留多色图标原有颜色时使用
+    /// `SvgIcon::from_bytes(..)?.with_original_colors()` 创建后注册。
+    #[cfg(feature = "svg")]
+    pub fn register_svg(&mut self, key: impl Into<String>, svg: SvgIcon) -> &mut Self {
+        self.register(key, Icon::Svg(Arc::new(svg)))
+    }
+
+    /// 注册 SVG 图标（字节数据），作为单色蒙版使用图标颜色着色
+    #[cfg(feature = "svg")]
+    pub fn register_svg_bytes(
+        &mut self,
+        key: impl Into<String>,
+        bytes: &[u8],
+    ) -> Result<&mut Self, SvgIconError> {
+        let key = key.into();
+        let svg = SvgIcon::from_bytes(key.clone(), bytes)?;
+        Ok(self.register_svg(key, svg))
+    }
+
+    /// 注册 SVG 图标（文件路径），作为单色蒙版使用图标颜色着色
+    #[cfg(feature = "svg")]
+    pub fn register_svg_file(
+        &mut self,
+        key: impl Into<String>,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<&mut Self, SvgIconError> {
+        let svg = SvgIcon::from_path(path)?;
+        Ok(self.register_svg(key, svg))
+    }
+
+    /// 移除图标
+    pub fn unregister(&mut self, key: &str) -> Option<Icon> {
+        self.icons.remove(key)
+    }
+
+    /// 查找图标
+    pub fn get(&self, key: &str) -> Option<&Icon> {
+        self.icons.get(key)
+    }
+
+    /// 是否注册了指定图标
+    pub fn contains(&self, key: &str) -> bool {
+        self.icons.contains_key(key)
+    }
+
+    /// 绘制指定图标，未注册时返回 `false`
+    pub fn paint(&self, painter: &Painter, key: &str, rect: Rect, color: Color32) -> bool {
+        match self.get(key) {
+            Some(icon) => {
+                icon.paint(painter, rect, color);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// 内置绘制图标留出边距
+fn inset(rect: Rect) -> Rect {
+    Rect::from_center_size(rect.center(), rect.size() * 0.8)
+}
+
+/// 绘制树形结构图标
+fn draw_tree_icon(painter: &Painter, rect: Rect, color: Color32) {
+    let stroke = egui::Stroke::new(1.5, color);
+    let x = rect.left() + rect.width() * 0.2;
+    let y_start = rect.top() + rect.height() * 0.2;
+    let y_end = rect.bottom() - rect.height() * 0.2;
+
+    // 主干
+    painter.line_segment(
+        [egui::Pos2::new(x, y_start), egui::Pos2::new(x, y_end)],
+        stroke,
+    );
+
+    // 分支
+    for i in 0..3 {
+        let y = y_start + (y_end - y_start) * (i as f32 + 0.5) / 3.0;
+        let x_end = rect.right() - rect.width() * 0.2;
+        painter.line_segment(
+            [
+                egui::Pos2::new(x, y),
+                egui::Pos2::new(x + rect.width() * 0.2, y),
+            ],
+            stroke,
+        );
+        painter.circle_filled(egui::Pos2::new(x_end, y), 2.0, color);
+    }
+}
+
+/// 绘制列表图标
+fn draw_list_icon(painter: &Painter, rect: Rect, color: Color32) {
+    for i in 0..3 {
+        let y = rect.top() + rect.height() * (i as f32 + 1.0) / 4.0;
+        let line_rect = egui::Rect::from_min_size(
+            egui::Pos2::new(rect.left() + rect.width() * 0.2, y - 1.0),
+            egui::Vec2::new(rect.width() * 0.6, 2.0),
+        );
+        painter.rect_filled(line_rect, 1.0, color);
+    }
+}
+
+/// 绘制带提示符的终端图标
+fn draw_prompt_icon(painter: &Painter, rect: Rect, color: Color32) {
+    let stroke = egui::Stroke::new(1.5, color);
+    let terminal_rect = rect.shrink(rect.width() * 0.15);
+    painter.rect_stroke(terminal_rect, 2.0, stroke, egui::StrokeKind::Outside);
+
+    // 提示符
+    let prompt_pos = terminal_rect.min
+        + egui::Vec2::new(terminal_rect.width() * 0.1, terminal_rect.height() * 0.6);
+    painter.text(
+        prompt_pos,
+        egui::Align2::LEFT_CENTER,
+        ">_",
+        egui::FontId::proportional(rect.height() * 0.7),
+        color,
+    );
+}
+
+/// 绘制场景树图标
+fn draw_scene_tree_icon(painter: &egui::Painter, rect: egui::Rect, color: egui::Color32) {
+    let stroke = egui::Stroke::new(1.5, color);
+    let line_height = rect.height() / 6.0;
+    let indent = rect.width() * 0.15;
+
+    // 垂直连接线
+    let x = rect.left() + indent;
+    painter.line_segment(
+        [
+            egui::Pos2::new(x, rect.top() + line_height),
+            egui::Pos2::new(x, rect.bottom() - line_height),
+        ],
+        stroke,
+    );
+
+    // 水平线和节点
+    for i in 0..3 {
+        let y = rect.top() + line_height * (2.0 + i as f32 * 2.0);
+        let node_x = x + indent;
+
+        // 水平连接线
+        painter.line_segment([egui::Pos2::new(x, y), egui::Pos2::new(node_x, y)], stroke);
+
+        // 节点矩形
+        let node_rect = egui::Rect::from_min_size(
+            egui::Pos2::new(node_x, y - line_height * 0.3),
+            egui::Vec2::new(rect.width() - indent * 2.5, line_height * 0.6),
+        );
+        painter.rect_filled(node_rect, 2.0, color);
+    }
+}
+
+/// 绘制属性图标
+fn draw_properties_icon(painter: &egui::Painter, rect: egui::Rect, color: egui::Color32) {
+    let stroke = egui::Stroke::new(1.5, color);
+    let margin = rect.width() * 0.1;
+    let content_rect = rect.shrink(margin);
+
+    // 外框
+    painter.rect_stroke(content_rect, 3.0, stroke, egui::StrokeKind::Outside);
+
+    // 内容线条
+    let line_height = content_rect.height() / 6.0;
+    for i in 0..3 {
+        let y = content_rect.top() + line_height * (1.5 + i as f32 * 1.5);
+        let line_width = content_rect.width() * (0.8 - i as f32 * 0.1);
+        let line_rect = egui::Rect::from_min_size(
+            egui::Pos2::new(content_rect.left() + margin, y - 1.0),
+            egui::Vec2::new(line_width, 2.0),
+        );
+        painter.rect_filled(line_rect, 1.0, color);
+    }
+}
+
+/// 绘制控制台图标
+fn draw_console_icon(painter: &egui::Painter, rect: egui::Rect, color: egui::Color32) {
+    let stroke = egui::Stroke::new(1.5, color);
+    let margin = rect.width() * 0.05;
+    let console_rect = rect.shrink(margin);
+
+    // 控制台外框
+    painter.rect_stroke(console_rect, 3.0, stroke, egui::StrokeKind::Outside);
+
+    // 命令提示符 ">"
+    let prompt_size = console_rect.width() * 0.15;
+    let prompt_center = egui::Pos2::new(console_rect.left() + prompt_size, console_rect.center().y);
+
+    // 绘制三角形提示符
+    let triangle_points = [
+        egui::Pos2::new(
+            prompt_center.x - prompt_size * 0.3,
+            prompt_center.y - prompt_size * 0.3,
+        ),
+        egui::Pos2::new(prompt_center.x + prompt_size * 0.3, prompt_center.y),
+        egui::Pos2::new(
+            prompt_center.x - prompt_size * 0.3,
+            prompt_center.y + prompt_size * 0.3,
+        ),
+    ];
+    painter.add(egui::Shape::convex_polygon(
+        triangle_points.to_vec(),
+        color,
+        egui::Stroke::NONE,
+    ));
+
+    // 命令行
+    let line_rect = egui::Rect::from_min_size(
+        egui::Pos2::new(prompt_center.x + prompt_size, prompt_center.y - 1.0),
+        egui::Vec2::new(console_rect.width() * 0.5, 2.0),
+    );
+    painter.rect_filled(line_rect, 1.0, color);
+}
+
+/// 绘制文件管理器图标 (Files)
+fn draw_files_icon(painter: &egui::Painter, rect: egui::Rect, color: egui::Color32) {
+    let stroke = egui::Stroke::new(1.5, color);
+
+    // 后面的文件夹
+    let back_folder = egui::Rect::from_min_size(
+        rect.min + egui::Vec2::new(rect.width() * 0.1, rect.height() * 0.3),
+        egui::Vec2::new(rect.width() * 0.6, rect.height() * 0.5),
+    );
+    painter.rect_stroke(back_folder, 2.0, stroke, egui::StrokeKind::Outside);
+
+    // 前面的文件夹
+    let front_folder = egui::Rect::from_min_size(
+        rect.min + egui::Vec2::new(rect.width() * 0.3, rect.height() * 0.15),
+        egui::Vec2::new(rect.width() * 0.6, rect.height() * 0.5),
+    );
+    painter.rect_filled(front_folder, 2.0, color.gamma_multiply(0.1));
+    painter.rect_stroke(front_folder, 2.0, stroke, egui::StrokeKind::Outside);
+
+    // 文件夹标签
+    let tab_rect = egui::Rect::from_min_size(
+        front_folder.min - egui::Vec2::new(0.0, rect.height() * 0.08),
+        egui::Vec2::new(rect.width() * 0.25, rect.height() * 0.08),
+    );
+    painter.rect_filled(tab_rect, 1.0, color.gamma_multiply(0.15));
+}
+
+/// 绘制终端图标 (Terminal)
+fn draw_terminal_icon(painter: &egui::Painter, rect: egui::Rect, color: egui::Color32) {
+    let stroke = egui::Stroke::new(1.5, color);
+
+    // 终端窗口边框
+    let terminal_rect = rect.shrink(rect.width() * 0.1);
+    painter.rect_stroke(terminal_rect, 3.0, stroke, egui::StrokeKind::Outside);
+
+    // 命令提示符 ">"
+    let prompt_center = egui::Pos2::new(
+        terminal_rect.left() + terminal_rect.width() * 0.2,
+        terminal_rect.center().y,
+    );
+
+    // 绘制 ">" 符号
+    painter.line_segment(
+        [
+            egui::Pos2::new(prompt_center.x - 5.0, prompt_center.y - 5.0),
+            egui::Pos2::new(prompt_center.x, prompt_center.y),
+        ],
+        stroke,
+    );
+    painter.line_segment(
+        [
+            egui::Pos2::new(prompt_center.x - 5.0, prompt_center.y + 5.0),
+            egui::Pos2::new(prompt_center.x, prompt_center.y),
+        ],
+        stroke,
+    );
+
+    // 光标
+    let cursor_rect = egui::Rect::from_min_size(
+        egui::Pos2::new(prompt_center.x + 10.0, prompt_center.y - 1.0),
+        egui::Vec2::new(8.0, 2.0),
+    );
+    painter.rect_filled(cursor_rect, 0.0, color);
+}
+
+/// 绘制设置图标 (Settings)
+fn draw_settings_icon(painter: &egui::Painter, rect: egui::Rect, color: egui::Color32) {
+    let center = rect.center();
+    let radius = rect.width().min(rect.height()) * 0.35;
+
+    // 绘制齿轮形状（简化版）
+    // 中心圆
+    painter.circle_filled(center, radius * 0.4, color.gamma_multiply(0.1));
+    painter.circle_stroke(center, radius * 0.4, egui::Stroke::new(1.5, color));
+
+    // 齿轮齿
+    let teeth_count = 8;
+    for i in 0..teeth_count {
+        let angle = (i as f32) * std::f32::consts::TAU / (teeth_count as f32);
+        let tooth_inner = center + egui::Vec2::angled(angle) * (radius * 0.5);
+        let tooth_outer = center + egui::Vec2::angled(angle) * radius;
+
+        painter.line_segment([tooth_inner, tooth_outer], egui::Stroke::new(2.0, color));
+    }
+}
+
+/// 绘制关闭图标 (Close)
+fn draw_close_icon(painter: &egui::Painter, rect: egui::Rect, color: egui::Color32) {
+    let stroke = egui::Stroke::new(2.0, color);
+    let center = rect.center();
+    let size = rect.width().min(rect.height()) * 0.4;
+
+    // 绘制 X 符号的两条对角线
+    let half_size = size * 0.5;
+
+    // 左上到右下的对角线
+    painter.line_segment(
+        [
+            egui::Pos2::new(center.x - half_size, center.y - half_size),
+            egui::Pos2::new(center.x + half_size, center.y + half_size),
+        ],
+        stroke,
+    );
+
+    // 右上到左下的对角线
+    painter.line_segment(
+        [
+            egui::Pos2::new(center.x + half_size, center.y - half_size),
+            egui::Pos2::new(center.x - half_size, center.y + half_size),
+        ],
+        stroke,
+    );
+}
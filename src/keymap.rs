@@ -0,0 +1,301 @@
+use egui::{Key, KeyboardShortcut, Modifiers};
+use serde::{Deserialize, Serialize};
+
+use crate::dock_collapsible::PanelSide;
+
+/// 快捷键，序列化为 `"Ctrl+Shift+B"` 形式的字符串
+///
+/// 支持的修饰键：`Ctrl`、`Shift`、`Alt`、`Cmd`（macOS 上为 ⌘，其他平台为 Ctrl）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Shortcut {
+    /// 修饰键
+    pub modifiers: Modifiers,
+    /// 按键
+    pub key: Key,
+}
+
+impl Shortcut {
+    /// 创建快捷键
+    pub const fn new(modifiers: Modifiers, key: Key) -> Self {
+        Self { modifiers, key }
+    }
+
+    /// 转换为 egui 快捷键
+    pub fn to_keyboard_shortcut(self) -> KeyboardShortcut {
+        KeyboardShortcut::new(self.modifiers, self.key)
+    }
+
+    /// 按下 `pressed` 时 egui 是否会匹配该快捷键
+    ///
+    /// egui 匹配时忽略多余的 Shift/Alt，并且在 macOS 以外的平台上 Ctrl 同时视为 Cmd，
+    /// 因此按下 `Ctrl+Alt+B` 也会匹配 `Ctrl+B`，`Ctrl+B` 和 `Cmd+B` 互相匹配。
+    pub fn matches(self, pressed: Shortcut) -> bool {
+        if self.key != pressed.key {
+            return false;
+        }
+        if self == pressed {
+            return true;
+        }
+        let mut modifiers = pressed.modifiers;
+        if modifiers.ctrl || modifiers.command {
+            modifiers.ctrl = true;
+            modifiers.command = true;
+        }
+        modifiers.matches_logically(self.modifiers)
+    }
+
+    /// 修饰键数量，用于优先匹配更具体的快捷键
+    fn modifier_count(self) -> usize {
+        let Modifiers {
+            alt,
+            ctrl,
+            shift,
+            mac_cmd,
+            command,
+        } = self.modifiers;
+        [alt, ctrl, shift, mac_cmd, command]
+            .into_iter()
+            .filter(|pressed| *pressed)
+            .count()
+    }
+}
+
+impl std::fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let modifiers = [
+            (self.modifiers.ctrl, "Ctrl"),
+            (self.modifiers.command || self.modifiers.mac_cmd, "Cmd"),
+            (self.modifiers.shift, "Shift"),
+            (self.modifiers.alt, "Alt"),
+        ];
+        for (_, name) in modifiers.iter().filter(|(pressed, _)| *pressed) {
+            write!(f, "{name}+")?;
+        }
+        f.write_str(self.key.name())
+    }
+}
+
+impl std::str::FromStr for Shortcut {
+    type Err = ShortcutParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error = || ShortcutParseError(text.to_owned());
+        let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+        let key = parts.pop().and_then(Key::from_name).ok_or_else(error)?;
+
+        let mut modifiers = Modifiers::NONE;
+        for part in parts {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers.ctrl = true,
+                "cmd" | "command" => modifiers.command = true,
+                "shift" => modifiers.shift = true,
+                "alt" | "option" => modifiers.alt = true,
+                _ => return Err(error()),
+            }
+        }
+        Ok(Self::new(modifiers, key))
+    }
+}
+
+impl TryFrom<String> for Shortcut {
+    type Error = ShortcutParseError;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+impl From<Shortcut> for String {
+    fn from(shortcut: Shortcut) -> Self {
+        shortcut.to_string()
+    }
+}
+
+/// 无法解析的快捷键字符串
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortcutParseError(pub String);
+
+impl std::fmt::Display for ShortcutParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid shortcut: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ShortcutParseError {}
+
+/// 快捷键触发的面板操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyAction {
+    /// 切换面板折叠状态
+    TogglePanel,
+    /// 激活折叠栏中显示的第 n 个按钮（从 0 开始，按当前显示顺序）
+    FocusButton(usize),
+    /// 切换到焦点区域的下一个标签页
+    NextTab,
+    /// 切换到焦点区域的上一个标签页
+    PreviousTab,
+}
+
+/// 一条快捷键绑定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBinding {
+    /// 快捷键
+    pub shortcut: Shortcut,
+    /// 操作
+    pub action: KeyAction,
+}
+
+/// 快捷键绑定表
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keymap {
+    /// 所有绑定，按添加顺序排列
+    pub bindings: Vec<KeyBinding>,
+}
+
+impl Keymap {
+    /// 创建空的绑定表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 按面板方向创建常用的默认绑定
+    ///
+    /// 左侧：`Ctrl+B` 切换，`Alt+1`..`Alt+9` 激活按钮；右侧：`Ctrl+Alt+R`；
+    /// 底部：`Ctrl+J`；顶部：`Ctrl+Alt+T`。各方向使用不同的按键，
+    /// 避免 egui 忽略多余修饰键时一个快捷键吞掉另一个。
+    pub fn default_for_side(side: PanelSide) -> Self {
+        let toggle = match side {
+            PanelSide::Left => Shortcut::new(Modifiers::CTRL, Key::B),
+            PanelSide::Right => Shortcut::new(Modifiers::CTRL | Modifiers::ALT, Key::R),
+            PanelSide::Bottom => Shortcut::new(Modifiers::CTRL, Key::J),
+            PanelSide::Top => Shortcut::new(Modifiers::CTRL | Modifiers::ALT, Key::T),
+        };
+        let mut keymap = Self::new().with_binding(toggle, KeyAction::TogglePanel);
+
+        if side == PanelSide::Left {
+            let digits = [
+                Key::Num1,
+                Key::Num2,
+                Key::Num3,
+                Key::Num4,
+                Key::Num5,
+                Key::Num6,
+                Key::Num7,
+                Key::Num8,
+                Key::Num9,
+            ];
+            for (index, key) in digits.into_iter().enumerate() {
+                keymap.bind(
+                    Shortcut::new(Modifiers::ALT, key),
+                    KeyAction::FocusButton(index),
+                );
+            }
+        }
+        keymap
+    }
+
+    /// 添加绑定
+    pub fn with_binding(mut self, shortcut: Shortcut, action: KeyAction) -> Self {
+        self.bind(shortcut, action);
+        self
+    }
+
+    /// 添加绑定，已存在的相同绑定不会重复添加
+    pub fn bind(&mut self, shortcut: Shortcut, action: KeyAction) {
+        let binding = KeyBinding { shortcut, action };
+        if !self.bindings.contains(&binding) {
+            self.bindings.push(binding);
+        }
+    }
+
+    /// 移除快捷键的所有绑定
+    pub fn unbind(&mut self, shortcut: Shortcut) {
+        self.bindings.retain(|binding| binding.shortcut != shortcut);
+    }
+
+    /// 获取快捷键绑定的所有操作
+    pub fn actions(&self, shortcut: Shortcut) -> impl Iterator<Item = KeyAction> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |binding| binding.shortcut == shortcut)
+            .map(|binding| binding.action)
+    }
+
+    /// 按匹配优先级排列的不重复快捷键（修饰键多的优先）
+    pub(crate) fn shortcuts_by_priority(&self) -> Vec<Shortcut> {
+        shortcuts_by_priority([self])
+    }
+}
+
+/// 多个绑定表中按匹配优先级排列的不重复快捷键（修饰键多的优先）
+///
+/// egui 匹配时忽略多余的 Shift/Alt，必须先检查更具体的快捷键。
+pub(crate) fn shortcuts_by_priority<'a>(
+    keymaps: impl IntoIterator<Item = &'a Keymap>,
+) -> Vec<Shortcut> {
+    let mut shortcuts: Vec<Shortcut> = Vec::new();
+    for binding in keymaps.into_iter().flat_map(|keymap| &keymap.bindings) {
+        if !shortcuts.contains(&binding.shortcut) {
+            shortcuts.push(binding.shortcut);
+        }
+    }
+    shortcuts.sort_by_key(|shortcut| std::cmp::Reverse(shortcut.modifier_count()));
+    shortcuts
+}
+
+/// 快捷键冲突：按下一个快捷键会匹配多个绑定
+///
+/// 除了完全相同的快捷键，egui 匹配时忽略多余的 Shift/Alt，并且在 macOS 以外的平台上
+/// Ctrl 等同于 Cmd，所以 `Ctrl+B` 和 `Ctrl+Alt+B`、`Ctrl+B` 和 `Cmd+B` 也会冲突。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeymapConflict {
+    /// 按下后匹配多个绑定的快捷键
+    pub shortcut: Shortcut,
+    /// 匹配的面板和绑定
+    pub bindings: Vec<(PanelSide, KeyBinding)>,
+}
+
+impl std::fmt::Display for KeymapConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} triggers", self.shortcut)?;
+        for (index, (side, binding)) in self.bindings.iter().enumerate() {
+            let separator = if index == 0 { " " } else { ", " };
+            write!(f, "{separator}{side:?} {:?}", binding.action)?;
+            if binding.shortcut != self.shortcut {
+                write!(f, " ({})", binding.shortcut)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 查找多个面板绑定表之间（以及各表内部）的快捷键冲突
+pub fn find_conflicts<'a>(
+    keymaps: impl IntoIterator<Item = (PanelSide, &'a Keymap)>,
+) -> Vec<KeymapConflict> {
+    let bindings: Vec<(PanelSide, KeyBinding)> = keymaps
+        .into_iter()
+        .flat_map(|(side, keymap)| keymap.bindings.iter().map(move |binding| (side, *binding)))
+        .collect();
+
+    let mut conflicts: Vec<KeymapConflict> = Vec::new();
+    for (_, pressed) in &bindings {
+        let matched: Vec<(PanelSide, KeyBinding)> = bindings
+            .iter()
+            .filter(|(_, binding)| binding.shortcut.matches(pressed.shortcut))
+            .copied()
+            .collect();
+        // `Ctrl+B` 和 `Cmd+B` 互相匹配，同一组绑定只报告一次
+        let reported = conflicts
+            .iter()
+            .any(|conflict| conflict.bindings == matched);
+        if matched.len() > 1 && !reported {
+            conflicts.push(KeymapConflict {
+                shortcut: pressed.shortcut,
+                bindings: matched,
+            });
+        }
+    }
+    conflicts
+}
@@ -0,0 +1,277 @@
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+use crate::dock_collapsible::CollapsibleDockState;
+
+/// 当前布局文件格式版本
+pub const LAYOUT_FILE_VERSION: u32 = 7;
+
+/// 版本迁移函数，将版本 `n` 的文件内容转换为版本 `n + 1`
+type Migration = fn(Value) -> Result<Value, String>;
+
+/// 迁移链，`MIGRATIONS[n]` 将版本 `n` 迁移到版本 `n + 1`
+///
+/// 修改持久化的结构（例如为 `PanelState` 添加字段）时，提升
+/// [`LAYOUT_FILE_VERSION`] 并在末尾追加一个迁移函数，旧文件会依次迁移到最新版本。
+const MIGRATIONS: [Migration; LAYOUT_FILE_VERSION as usize] = [
+    migrate_v0_to_v1,
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+    migrate_v4_to_v5,
+    migrate_v5_to_v6,
+    migrate_v6_to_v7,
+];
+
+/// 版本 0：没有版本号，直接保存的 `CollapsibleDockState`
+fn migrate_v0_to_v1(layout: Value) -> Result<Value, String> {
+    if !layout.is_object() {
+        return Err("expected a layout object".to_owned());
+    }
+    let mut file = Map::new();
+    file.insert("version".to_owned(), Value::from(1));
+    file.insert("layout".to_owned(), layout);
+    Ok(Value::Object(file))
+}
+
+/// 版本 2：`PanelState` 新增 `pinned`，旧布局中的面板都是固定的
+fn migrate_v1_to_v2(file: Value) -> Result<Value, String> {
+    insert_panel_field(file, "pinned", Value::Bool(true))
+}
+
+/// 版本 3：`PanelState` 新增 `button_order`，旧布局按添加顺序显示按钮
+fn migrate_v2_to_v3(file: Value) -> Result<Value, String> {
+    insert_panel_field(file, "button_order", Value::Array(Vec::new()))
+}
+
+/// 版本 4：`PanelState` 新增 `hidden_buttons`，旧布局中的按钮都可见
+fn migrate_v3_to_v4(file: Value) -> Result<Value, String> {
+    insert_panel_field(file, "hidden_buttons", Value::Array(Vec::new()))
+}
+
+/// 版本 5：新增 `side_overrides`，旧布局中的面板都在创建时的方向
+fn migrate_v4_to_v5(file: Value) -> Result<Value, String> {
+    insert_layout_field(file, "side_overrides", Value::Object(Map::new()))
+}
+
+/// 版本 6：新增按面板 ID 保存的 `keyed_panels`
+fn migrate_v5_to_v6(file: Value) -> Result<Value, String> {
+    insert_layout_field(file, "keyed_panels", Value::Object(Map::new()))
+}
+
+/// 版本 7：`PanelState` 新增 `auto_collapsed`，旧布局中的面板都不是自动折叠的
+fn migrate_v6_to_v7(file: Value) -> Result<Value, String> {
+    insert_panel_field(file, "auto_collapsed", Value::Bool(false))
+}
+
+/// 为布局补充新增字段，已有的值保持不变
+fn insert_layout_field(mut file: Value, field: &str, value: Value) -> Result<Value, String> {
+    let layout = file
+        .get_mut("layout")
+        .and_then(Value::as_object_mut)
+        .ok_or("missing layout")?;
+    layout.entry(field).or_insert(value);
+    Ok(file)
+}
+
+/// 为每个面板状态（包括按面板 ID 保存的面板）补充新增字段，已有的值保持不变
+fn insert_panel_field(mut file: Value, field: &str, value: Value) -> Result<Value, String> {
+    let panels = file
+        .pointer_mut("/layout/panels")
+        .and_then(Value::as_object_mut)
+        .ok_or("missing layout.panels")?;
+    for panel in panels.values_mut() {
+        if let Some(panel) = panel.as_object_mut() {
+            panel.entry(field).or_insert_with(|| value.clone());
+        }
+    }
+    let keyed_panels = file
+        .pointer_mut("/layout/keyed_panels")
+        .and_then(Value::as_object_mut);
+    for panel in keyed_panels
+        .into_iter()
+        .flat_map(|panels| panels.values_mut())
+    {
+        if let Some(panel) = panel.pointer_mut("/state").and_then(Value::as_object_mut) {
+            panel.entry(field).or_insert_with(|| value.clone());
+        }
+    }
+    Ok(file)
+}
+
+/// 布局文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutFormat {
+    /// RON 格式
+    Ron,
+    /// JSON 格式
+    Json,
+}
+
+impl LayoutFormat {
+    /// 根据文件扩展名（`.ron` / `.json`）判断格式
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?;
+        if extension.eq_ignore_ascii_case("ron") {
+            Some(Self::Ron)
+        } else if extension.eq_ignore_ascii_case("json") {
+            Some(Self::Json)
+        } else {
+            None
+        }
+    }
+
+    /// 将文件内容序列化为字符串
+    fn write(self, file: &Value) -> Result<String, LayoutFileError> {
+        match self {
+            Self::Ron => ron::ser::to_string_pretty(file, ron::ser::PrettyConfig::default())
+                .map_err(LayoutFileError::RonWrite),
+            Self::Json => serde_json::to_string_pretty(file).map_err(LayoutFileError::Json),
+        }
+    }
+
+    /// 将字符串解析为文件内容
+    fn read(self, text: &str) -> Result<Value, LayoutFileError> {
+        match self {
+            Self::Ron => ron::from_str(text).map_err(LayoutFileError::RonRead),
+            Self::Json => serde_json::from_str(text).map_err(LayoutFileError::Json),
+        }
+    }
+}
+
+/// 布局文件读写错误
+#[derive(Debug)]
+pub enum LayoutFileError {
+    /// 读写文件失败
+    Io(std::io::Error),
+    /// 无法根据扩展名判断文件格式
+    UnknownFormat(PathBuf),
+    /// RON 解析失败
+    RonRead(ron::error::SpannedError),
+    /// RON 序列化失败
+    RonWrite(ron::Error),
+    /// JSON 解析、序列化或结构不匹配
+    Json(serde_json::Error),
+    /// 文件版本比当前库支持的版本新
+    UnsupportedVersion { found: u32, supported: u32 },
+    /// 迁移旧版本文件失败
+    Migration { from: u32, message: String },
+}
+
+impl std::fmt::Display for LayoutFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to access layout file: {err}"),
+            Self::UnknownFormat(path) => write!(
+                f,
+                "unknown layout file format for {} (expected .ron or .json)",
+                path.display()
+            ),
+            Self::RonRead(err) => write!(f, "failed to parse RON layout: {err}"),
+            Self::RonWrite(err) => write!(f, "failed to write RON layout: {err}"),
+            Self::Json(err) => write!(f, "invalid JSON layout: {err}"),
+            Self::UnsupportedVersion { found, supported } => write!(
+                f,
+                "layout file version {found} is newer than supported version {supported}"
+            ),
+            Self::Migration { from, message } => {
+                write!(f, "failed to migrate layout from version {from}: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LayoutFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::RonRead(err) => Some(err),
+            Self::RonWrite(err) => Some(err),
+            Self::Json(err) => Some(err),
+            Self::UnknownFormat(_) | Self::UnsupportedVersion { .. } | Self::Migration { .. } => {
+                None
+            }
+        }
+    }
+}
+
+/// 读取文件内容的版本号，没有版本号的文件视为版本 0
+fn file_version(file: &Value) -> Result<u32, LayoutFileError> {
+    match file.get("version") {
+        None => Ok(0),
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| LayoutFileError::Migration {
+                from: 0,
+                message: format!("invalid version field: {version}"),
+            }),
+    }
+}
+
+/// 依次执行迁移，直到文件内容达到当前版本
+fn migrate(mut file: Value) -> Result<Value, LayoutFileError> {
+    let mut version = file_version(&file)?;
+    if version > LAYOUT_FILE_VERSION {
+        return Err(LayoutFileError::UnsupportedVersion {
+            found: version,
+            supported: LAYOUT_FILE_VERSION,
+        });
+    }
+
+    while version < LAYOUT_FILE_VERSION {
+        file =
+            MIGRATIONS[version as usize](file).map_err(|message| LayoutFileError::Migration {
+                from: version,
+                message,
+            })?;
+        version += 1;
+        if let Some(object) = file.as_object_mut() {
+            object.insert("version".to_owned(), Value::from(version));
+        }
+    }
+    Ok(file)
+}
+
+impl CollapsibleDockState {
+    /// 序列化为带版本号的布局文件内容
+    pub fn to_layout_string(&self, format: LayoutFormat) -> Result<String, LayoutFileError> {
+        let mut file = Map::new();
+        file.insert("version".to_owned(), Value::from(LAYOUT_FILE_VERSION));
+        file.insert(
+            "layout".to_owned(),
+            serde_json::to_value(self).map_err(LayoutFileError::Json)?,
+        );
+        format.write(&Value::Object(file))
+    }
+
+    /// 解析布局文件内容，旧版本会先迁移到当前版本
+    pub fn from_layout_str(text: &str, format: LayoutFormat) -> Result<Self, LayoutFileError> {
+        let mut file = migrate(format.read(text)?)?;
+        let layout =
+            file.get_mut("layout")
+                .map(Value::take)
+                .ok_or_else(|| LayoutFileError::Migration {
+                    from: LAYOUT_FILE_VERSION,
+                    message: "missing layout field".to_owned(),
+                })?;
+        serde_json::from_value(layout).map_err(LayoutFileError::Json)
+    }
+
+    /// 保存到布局文件，格式由扩展名决定
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), LayoutFileError> {
+        let path = path.as_ref();
+        let format = LayoutFormat::from_path(path)
+            .ok_or_else(|| LayoutFileError::UnknownFormat(path.to_owned()))?;
+        std::fs::write(path, self.to_layout_string(format)?).map_err(LayoutFileError::Io)
+    }
+
+    /// 从布局文件加载，格式由扩展名决定
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self, LayoutFileError> {
+        let path = path.as_ref();
+        let format = LayoutFormat::from_path(path)
+            .ok_or_else(|| LayoutFileError::UnknownFormat(path.to_owned()))?;
+        let text = std::fs::read_to_string(path).map_err(LayoutFileError::Io)?;
+        Self::from_layout_str(&text, format)
+    }
+}
@@ -14,15 +14,34 @@
 //! - **Phosphor icons**: Beautiful icons from egui-phosphor for a professional look
 //! - **re_ui integration**: Works seamlessly with the rerun design system
 //! - **Flexible layout**: Supports left, right, top, and bottom panels
+//! - **Lightweight toolbar**: `CollapsibleToolbar` manages its own tabs without egui_dock
+//! - **Layout files**: Save and load versioned layouts as standalone RON/JSON files
+//! - **Keyboard shortcuts**: Serializable per-panel keymaps with conflict diagnostics
+//! - **Overlay mode**: Show collapsed panels floating over the central area without reflowing it
+//! - **Badges**: Show counts or dots with severity colors on strip buttons
+//! - **Reorderable buttons**: Drag strip buttons to reorder them; the order is persisted by button id
+//! - **Strip context menu**: Hide buttons, move the panel, reset its size, plus app-defined entries
+//! - **Movable panels**: Drag a panel to another window edge; the new side is persisted
+//! - **Responsive collapse**: Breakpoints and priorities collapse panels on small windows and restore them
+//! - **Drag to collapse/expand**: Drag the resize edge past a snap threshold to collapse, or out of the strip to expand
+//! - **Separator double-click**: Cycle between the saved size, the default size, maximized and collapsed
+//! - **Pin/unpin**: Unpinned panels auto-collapse when the pointer leaves or on outside clicks
+//! - **Layout presets**: Save named layouts and switch between them with animation
+//! - **Cross-panel tab drag**: Drag tabs between panels, collapsed strips and the central dock
+//! - **Several panels per side**: Panels with an id keep their own state and sit side by side from the window edge inward
+//! - **Localization**: Built-in UI strings in English and Chinese, with per-entry overrides
+//! - **Workspace**: `CollapsibleWorkspace` renders all four sides and the central dock in one call
+//! - **SVG icons** (feature `svg`): Render SVG assets as tinted strip button icons
 //!
 //! ## Example
 //!
 //! ```rust,no_run
 //! use egui_collapsible_dock::{CollapsibleDockPanel, CollapsibleButton, PanelSide};
-//! use egui_dock::DockState;
+//! use egui_dock::{DockState, TabViewer};
 //!
+//! # fn example<V: TabViewer>(ctx: &egui::Context, dock_state: DockState<V::Tab>, tab_viewer: &mut V) {
 //! // Create a collapsible left panel
-//! let left_panel = CollapsibleDockPanel::new(
+//! let mut left_panel = CollapsibleDockPanel::new(
 //!     PanelSide::Left,
 //!     egui::Id::new("left_panel"),
 //! )
@@ -35,12 +54,41 @@
 //! );
 //!
 //! // Show the panel in your egui update loop
-//! left_panel.show(ctx, &mut tab_viewer);
+//! left_panel.show(ctx, tab_viewer);
+//! # }
 //! ```
 
+pub mod animation;
+pub mod badge;
+pub mod collapsible_toolbar;
 pub mod dock_collapsible;
+pub mod icon_registry;
+pub mod keymap;
+pub mod layout_file;
+pub mod preset;
+pub mod strings;
+#[cfg(feature = "svg")]
+pub mod svg_icon;
+pub mod tab_drag;
+pub mod workspace;
 
 // Re-export main types for convenience
+pub use animation::Easing;
+pub use badge::{Badge, BadgeContent, BadgeSeverity};
+pub use collapsible_toolbar::{CollapsibleToolbar, ToolbarState, ToolbarTabViewer};
 pub use dock_collapsible::{
-    CollapsibleButton, CollapsibleDockPanel, CollapsibleDockState, PanelSide, PanelState,
+    Breakpoint, CollapsibleButton, CollapsibleDockPanel, CollapsibleDockState, ExpandMode,
+    KeyedPanelState, PanelEvent, PanelKey, PanelSide, PanelState, StripMenuCallback, TabPredicate,
+    TransitionMode,
 };
+pub use icon_registry::{Icon, IconPainter, IconRegistry};
+pub use keymap::{
+    find_conflicts, KeyAction, KeyBinding, Keymap, KeymapConflict, Shortcut, ShortcutParseError,
+};
+pub use layout_file::{LayoutFileError, LayoutFormat, LAYOUT_FILE_VERSION};
+pub use preset::LayoutPreset;
+pub use strings::{Locale, StringKey, Strings};
+#[cfg(feature = "svg")]
+pub use svg_icon::{SvgIcon, SvgIconError};
+pub use tab_drag::{DockTarget, TabDragViewer};
+pub use workspace::{CollapsibleWorkspace, CornerOwnership, WorkspaceEvent};
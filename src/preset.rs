@@ -0,0 +1,21 @@
+use egui_dock::DockState;
+use std::collections::HashMap;
+
+use crate::dock_collapsible::{CollapsibleDockState, PanelSide};
+
+/// 命名布局预设，记录工作区在某一时刻的完整布局
+#[derive(Clone)]
+pub struct LayoutPreset<Tab> {
+    /// 各个面板的折叠状态和尺寸
+    pub state: CollapsibleDockState,
+    /// 各个方向上未设置面板 ID 的面板的活动按钮
+    pub active_buttons: HashMap<PanelSide, Option<usize>>,
+    /// 各个方向上未设置面板 ID 的面板内的标签页布局
+    pub panel_docks: HashMap<PanelSide, DockState<Tab>>,
+    /// 设置了面板 ID 的面板的活动按钮
+    pub keyed_active_buttons: HashMap<String, Option<usize>>,
+    /// 设置了面板 ID 的面板内的标签页布局
+    pub keyed_panel_docks: HashMap<String, DockState<Tab>>,
+    /// 中央区域的标签页布局
+    pub central: DockState<Tab>,
+}
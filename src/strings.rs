@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+/// 内置界面文本的键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StringKey {
+    /// 展开面板（折叠栏按钮提示和菜单项）
+    ExpandPanel,
+    /// 折叠面板（菜单项）
+    CollapsePanel,
+    /// 固定面板（图钉按钮提示）
+    PinPanel,
+    /// 取消固定面板（图钉按钮提示）
+    UnpinPanel,
+    /// 移动手柄的提示
+    DragToMovePanel,
+    /// 隐藏按钮，`{}` 替换为按钮文本
+    HideButton,
+    /// 显示隐藏的按钮（子菜单）
+    ShowHiddenButtons,
+    /// 全部显示
+    ShowAllButtons,
+    /// 移动到（子菜单）
+    MoveTo,
+    /// 左侧
+    SideLeft,
+    /// 右侧
+    SideRight,
+    /// 顶部
+    SideTop,
+    /// 底部
+    SideBottom,
+    /// 重置尺寸
+    ResetSize,
+    /// 关闭标签页（工具栏右键菜单）
+    CloseTab,
+    /// 收叠工具栏（工具栏按钮提示）
+    CollapseToolbar,
+}
+
+/// 内置文本的语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    /// 英文
+    English,
+    /// 中文
+    #[default]
+    Chinese,
+}
+
+impl Locale {
+    /// 该语言的内置文本
+    pub fn text(self, key: StringKey) -> &'static str {
+        match self {
+            Self::English => english(key),
+            Self::Chinese => chinese(key),
+        }
+    }
+}
+
+fn english(key: StringKey) -> &'static str {
+    match key {
+        StringKey::ExpandPanel => "Expand panel",
+        StringKey::CollapsePanel => "Collapse panel",
+        StringKey::PinPanel => "Pin panel",
+        StringKey::UnpinPanel => "Unpin panel",
+        StringKey::DragToMovePanel => "Drag to move the panel",
+        StringKey::HideButton => "Hide “{}”",
+        StringKey::ShowHiddenButtons => "Show hidden buttons",
+        StringKey::ShowAllButtons => "Show all",
+        StringKey::MoveTo => "Move to",
+        StringKey::SideLeft => "Left",
+        StringKey::SideRight => "Right",
+        StringKey::SideTop => "Top",
+        StringKey::SideBottom => "Bottom",
+        StringKey::ResetSize => "Reset size",
+        StringKey::CloseTab => "Close tab",
+        StringKey::CollapseToolbar => "Collapse toolbar",
+    }
+}
+
+fn chinese(key: StringKey) -> &'static str {
+    match key {
+        StringKey::ExpandPanel => "展开面板",
+        StringKey::CollapsePanel => "折叠面板",
+        StringKey::PinPanel => "固定面板",
+        StringKey::UnpinPanel => "取消固定面板",
+        StringKey::DragToMovePanel => "拖动以移动面板",
+        StringKey::HideButton => "隐藏“{}”",
+        StringKey::ShowHiddenButtons => "显示隐藏的按钮",
+        StringKey::ShowAllButtons => "全部显示",
+        StringKey::MoveTo => "移动到",
+        StringKey::SideLeft => "左侧",
+        StringKey::SideRight => "右侧",
+        StringKey::SideTop => "顶部",
+        StringKey::SideBottom => "底部",
+        StringKey::ResetSize => "重置尺寸",
+        StringKey::CloseTab => "关闭标签页",
+        StringKey::CollapseToolbar => "收叠工具栏",
+    }
+}
+
+/// 内置界面文本表：以某种语言为基础，可以覆盖任意条目
+///
+/// 默认使用中文。其他语言可以在英文或中文的基础上覆盖所有条目。
+#[derive(Debug, Clone, Default)]
+pub struct Strings {
+    /// 基础语言
+    locale: Locale,
+    /// 应用覆盖的条目
+    overrides: HashMap<StringKey, String>,
+}
+
+impl Strings {
+    /// 创建指定语言的文本表
+    pub fn new(locale: Locale) -> Self {
+        Self {
+            locale,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// 英文文本表
+    pub fn english() -> Self {
+        Self::new(Locale::English)
+    }
+
+    /// 中文文本表
+    pub fn chinese() -> Self {
+        Self::new(Locale::Chinese)
+    }
+
+    /// 覆盖一个条目
+    pub fn with(mut self, key: StringKey, text: impl Into<String>) -> Self {
+        self.set(key, text);
+        self
+    }
+
+    /// 覆盖一个条目
+    pub fn set(&mut self, key: StringKey, text: impl Into<String>) {
+        self.overrides.insert(key, text.into());
+    }
+
+    /// 获取基础语言
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// 获取条目文本，没有覆盖时使用基础语言
+    pub fn get(&self, key: StringKey) -> &str {
+        self.overrides
+            .get(&key)
+            .map_or_else(|| self.locale.text(key), String::as_str)
+    }
+
+    /// 获取条目文本并把 `{}` 替换为 `arg`
+    pub fn format(&self, key: StringKey, arg: &str) -> String {
+        self.get(key).replace("{}", arg)
+    }
+}
@@ -0,0 +1,144 @@
+use egui::{Color32, ColorImage, Context, Painter, Rect, TextureHandle, TextureOptions};
+use resvg::{tiny_skia, usvg};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// SVG 图标加载错误
+#[derive(Debug)]
+pub enum SvgIconError {
+    /// 读取文件失败
+    Io(std::io::Error),
+    /// SVG 解析失败
+    Parse(usvg::Error),
+}
+
+impl std::fmt::Display for SvgIconError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read SVG file: {err}"),
+            Self::Parse(err) => write!(f, "failed to parse SVG: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SvgIconError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(err) => Some(err),
+        }
+    }
+}
+
+/// 由 SVG 数据光栅化得到的图标
+///
+/// 纹理按物理像素尺寸缓存，`pixels_per_point` 或绘制尺寸变化时重新光栅化。
+pub struct SvgIcon {
+    /// 纹理名称
+    name: String,
+    /// 解析后的 SVG
+    tree: usvg::Tree,
+    /// 是否作为单色蒙版绘制（使用图标颜色着色）
+    monochrome: bool,
+    /// 按物理像素尺寸缓存的纹理
+    textures: Mutex<HashMap<[u32; 2], TextureHandle>>,
+}
+
+impl std::fmt::Debug for SvgIcon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SvgIcon")
+            .field("name", &self.name)
+            .field("monochrome", &self.monochrome)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SvgIcon {
+    /// 从 SVG 字节数据创建图标
+    pub fn from_bytes(name: impl Into<String>, bytes: &[u8]) -> Result<Self, SvgIconError> {
+        let tree =
+            usvg::Tree::from_data(bytes, &usvg::Options::default()).map_err(SvgIconError::Parse)?;
+        Ok(Self {
+            name: name.into(),
+            tree,
+            monochrome: true,
+            textures: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// 从 SVG 文件创建图标
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, SvgIconError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(SvgIconError::Io)?;
+        Self::from_bytes(path.display().to_string(), &bytes)
+    }
+
+    /// 保留 SVG 原有颜色，不使用图标颜色着色
+    pub fn with_original_colors(mut self) -> Self {
+        self.monochrome = false;
+        self
+    }
+
+    /// 在指定区域绘制图标
+    pub fn paint(&self, painter: &Painter, rect: Rect, color: Color32) {
+        let pixels_per_point = painter.pixels_per_point();
+        let size = [
+            (rect.width() * pixels_per_point).round().max(1.0) as u32,
+            (rect.height() * pixels_per_point).round().max(1.0) as u32,
+        ];
+
+        let Some(texture_id) = self.texture(painter.ctx(), size).map(|t| t.id()) else {
+            return;
+        };
+
+        let tint = if self.monochrome {
+            color
+        } else {
+            Color32::WHITE.gamma_multiply(color.a() as f32 / 255.0)
+        };
+        let uv = Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+        painter.image(texture_id, rect, uv, tint);
+    }
+
+    /// 获取（必要时光栅化）指定物理像素尺寸的纹理
+    fn texture(&self, ctx: &Context, size: [u32; 2]) -> Option<TextureHandle> {
+        let mut textures = self.textures.lock().ok()?;
+        if let Some(texture) = textures.get(&size) {
+            return Some(texture.clone());
+        }
+
+        let image = self.rasterize(size)?;
+        let texture = ctx.load_texture(
+            format!("{}@{}x{}", self.name, size[0], size[1]),
+            image,
+            TextureOptions::LINEAR,
+        );
+        textures.insert(size, texture.clone());
+        Some(texture)
+    }
+
+    /// 使用 resvg 在 CPU 上光栅化
+    fn rasterize(&self, [width, height]: [u32; 2]) -> Option<ColorImage> {
+        let mut pixmap = tiny_skia::Pixmap::new(width, height)?;
+        let tree_size = self.tree.size();
+        let transform = tiny_skia::Transform::from_scale(
+            width as f32 / tree_size.width(),
+            height as f32 / tree_size.height(),
+        );
+        resvg::render(&self.tree, transform, &mut pixmap.as_mut());
+
+        let size = [width as usize, height as usize];
+        if self.monochrome {
+            // 只保留 alpha 作为蒙版，颜色由着色决定
+            let rgba: Vec<u8> = pixmap
+                .data()
+                .chunks_exact(4)
+                .flat_map(|pixel| [255, 255, 255, pixel[3]])
+                .collect();
+            Some(ColorImage::from_rgba_unmultiplied(size, &rgba))
+        } else {
+            Some(ColorImage::from_rgba_premultiplied(size, pixmap.data()))
+        }
+    }
+}
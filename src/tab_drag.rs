@@ -0,0 +1,214 @@
+use egui::{Context, Id, LayerId, Order, Rect, Response, StrokeKind, Ui, WidgetText};
+use egui_dock::tab_viewer::OnCloseResponse;
+use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex, TabStyle, TabViewer};
+
+use crate::dock_collapsible::PanelSide;
+
+/// 工作区中的一个 `DockState`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DockTarget {
+    /// 中央区域
+    Central,
+    /// 指定方向未设置面板 ID 的可折叠面板
+    Panel(PanelSide),
+    /// 设置了面板 ID（[`with_panel_id`](crate::CollapsibleDockPanel::with_panel_id)）的可折叠面板
+    KeyedPanel(String),
+}
+
+/// 支持跨面板拖放标签页的 `TabViewer`
+pub trait TabDragViewer: TabViewer {
+    /// 标签页从一个 `DockState` 移动到另一个之后调用
+    fn on_tab_moved(&mut self, _tab: &mut Self::Tab, _from: DockTarget, _to: DockTarget) {}
+}
+
+/// 标签页在 `DockState` 中的位置
+pub(crate) type TabPath = (SurfaceIndex, NodeIndex, TabIndex);
+
+/// 正在拖动的标签页
+pub(crate) struct TabDrag {
+    /// 标签页所在的 `DockState`
+    pub from: DockTarget,
+    /// 标签页在 `from` 中的位置
+    pub path: TabPath,
+    /// 上一帧指针所在的放置区域，松开时标签页移动到这里
+    pub hovered: Option<DockTarget>,
+}
+
+/// 本帧可以接收标签页的区域
+pub(crate) struct DropZone {
+    /// 区域对应的 `DockState`
+    pub target: DockTarget,
+    /// 面板（或折叠栏）的区域
+    pub rect: Rect,
+    /// 在区域的标签栏中按下、还没有松开指针的标签页
+    pub pressed_tab: Option<TabPath>,
+}
+
+/// 在标签栏中按下、还没有松开指针的标签页
+///
+/// egui_dock 拖动标签页时不再把原来的标签按钮交给 [`TabViewer::on_tab_button`]，
+/// 因此在按下时记录位置和按下的时间，同一次按下期间一直有效。
+#[derive(Default)]
+pub(crate) struct PressedTab(Option<(TabPath, f64)>);
+
+impl PressedTab {
+    /// 获取本次按下的标签页
+    pub fn get(&self, ctx: &Context) -> Option<TabPath> {
+        let (path, pressed_at) = self.0?;
+        let press_start = ctx.input(|input| input.pointer.press_start_time())?;
+        (press_start == pressed_at).then_some(path)
+    }
+
+    /// 通过 [`TabButtonTracker`] 显示 `DockArea` 之后记录按下的标签页
+    pub fn update<Tab>(
+        &mut self,
+        ctx: &Context,
+        dock_state: &DockState<Tab>,
+        pressed: Option<*const Tab>,
+    ) {
+        let press_start = ctx.input(|input| input.pointer.press_start_time());
+        if let (Some(path), Some(press_start)) = (
+            pressed.and_then(|tab| tab_path(dock_state, tab)),
+            press_start,
+        ) {
+            self.0 = Some((path, press_start));
+        }
+    }
+}
+
+/// 包装应用的 `TabViewer`，通过 [`TabViewer::on_tab_button`] 记录指针按在哪个标签按钮上
+///
+/// 其余方法全部转发给应用的 `TabViewer`。
+pub(crate) struct TabButtonTracker<'a, V: TabViewer> {
+    viewer: &'a mut V,
+    /// 指针按在其标签按钮上的标签页；只用于在显示结束后与 `DockState` 中的标签页比较地址
+    pub pressed: Option<*const V::Tab>,
+}
+
+impl<'a, V: TabViewer> TabButtonTracker<'a, V> {
+    pub fn new(viewer: &'a mut V) -> Self {
+        Self {
+            viewer,
+            pressed: None,
+        }
+    }
+}
+
+impl<V: TabViewer> TabViewer for TabButtonTracker<'_, V> {
+    type Tab = V::Tab;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+        self.viewer.title(tab)
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        self.viewer.ui(ui, tab);
+    }
+
+    fn context_menu(
+        &mut self,
+        ui: &mut Ui,
+        tab: &mut Self::Tab,
+        surface: SurfaceIndex,
+        node: NodeIndex,
+    ) {
+        self.viewer.context_menu(ui, tab, surface, node);
+    }
+
+    fn id(&mut self, tab: &mut Self::Tab) -> Id {
+        self.viewer.id(tab)
+    }
+
+    fn on_tab_button(&mut self, tab: &mut Self::Tab, response: &Response) {
+        if response.is_pointer_button_down_on() {
+            self.pressed = Some(tab as *const Self::Tab);
+        }
+        self.viewer.on_tab_button(tab, response);
+    }
+
+    fn on_close(&mut self, tab: &mut Self::Tab) -> OnCloseResponse {
+        self.viewer.on_close(tab)
+    }
+
+    fn is_closeable(&self, tab: &Self::Tab) -> bool {
+        self.viewer.is_closeable(tab)
+    }
+
+    #[allow(deprecated)]
+    fn closeable(&mut self, tab: &mut Self::Tab) -> bool {
+        self.viewer.closeable(tab)
+    }
+
+    fn force_close(&mut self, tab: &mut Self::Tab) -> bool {
+        self.viewer.force_close(tab)
+    }
+
+    fn on_add(&mut self, surface: SurfaceIndex, node: NodeIndex) {
+        self.viewer.on_add(surface, node);
+    }
+
+    fn on_rect_changed(&mut self, tab: &mut Self::Tab) {
+        self.viewer.on_rect_changed(tab);
+    }
+
+    fn add_popup(&mut self, ui: &mut Ui, surface: SurfaceIndex, node: NodeIndex) {
+        self.viewer.add_popup(ui, surface, node);
+    }
+
+    fn tab_style_override(&self, tab: &Self::Tab, global_style: &TabStyle) -> Option<TabStyle> {
+        self.viewer.tab_style_override(tab, global_style)
+    }
+
+    fn allowed_in_windows(&self, tab: &mut Self::Tab) -> bool {
+        self.viewer.allowed_in_windows(tab)
+    }
+
+    fn clear_background(&self, tab: &Self::Tab) -> bool {
+        self.viewer.clear_background(tab)
+    }
+
+    fn scroll_bars(&self, tab: &Self::Tab) -> [bool; 2] {
+        self.viewer.scroll_bars(tab)
+    }
+}
+
+/// 按地址查找标签页的位置
+fn tab_path<Tab>(dock_state: &DockState<Tab>, tab: *const Tab) -> Option<TabPath> {
+    let mut leaf = None;
+    let mut tab_index = 0;
+    dock_state.iter_all_tabs().find_map(|(location, other)| {
+        if leaf == Some(location) {
+            tab_index += 1;
+        } else {
+            leaf = Some(location);
+            tab_index = 0;
+        }
+        let (surface_index, node_index) = location;
+        std::ptr::eq(other, tab).then_some((surface_index, node_index, TabIndex(tab_index)))
+    })
+}
+
+/// 在前景层绘制放置区域，指针所在的区域高亮显示
+pub(crate) fn paint_drop_zones(
+    ctx: &Context,
+    zones: &[DropZone],
+    from: &DockTarget,
+    hovered: Option<&DockTarget>,
+) {
+    let painter = ctx.layer_painter(LayerId::new(
+        Order::Foreground,
+        Id::new("collapsible_dock_tab_drop_zones"),
+    ));
+    let selection = ctx.style().visuals.selection;
+
+    for zone in zones.iter().filter(|zone| zone.target != *from) {
+        if hovered == Some(&zone.target) {
+            painter.rect_filled(zone.rect, 0.0, selection.bg_fill.gamma_multiply(0.25));
+            painter.rect_stroke(zone.rect, 0.0, selection.stroke, StrokeKind::Inside);
+        } else {
+            let mut stroke = selection.stroke;
+            stroke.color = stroke.color.gamma_multiply(0.4);
+            painter.rect_stroke(zone.rect, 0.0, stroke, StrokeKind::Inside);
+        }
+    }
+}
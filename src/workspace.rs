@@ -0,0 +1,762 @@
+use egui::{Context, Frame, Id, Order, Pos2, Vec2};
+use egui_dock::{DockArea, DockState, TabViewer};
+
+use crate::dock_collapsible::{CollapsibleDockPanel, CollapsibleDockState, PanelEvent, PanelSide};
+use crate::keymap::{find_conflicts, shortcuts_by_priority, KeymapConflict};
+use crate::preset::LayoutPreset;
+use crate::tab_drag::{
+    paint_drop_zones, DockTarget, DropZone, PressedTab, TabButtonTracker, TabDrag, TabDragViewer,
+    TabPath,
+};
+
+/// 工作区中面板产生的事件：面板方向、面板 ID（未设置时为 `None`）和事件
+pub type WorkspaceEvent = (PanelSide, Option<String>, PanelEvent);
+
+/// 窗口角落归属
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CornerOwnership {
+    /// 左右面板占满窗口高度，顶部/底部面板位于两者之间
+    #[default]
+    SidePanels,
+    /// 顶部/底部面板占满窗口宽度，左右面板位于两者之间
+    TopBottomPanels,
+}
+
+impl CornerOwnership {
+    /// 面板渲染顺序，先渲染的面板拥有窗口角落
+    fn render_order(self) -> [PanelSide; 4] {
+        match self {
+            Self::SidePanels => [
+                PanelSide::Left,
+                PanelSide::Right,
+                PanelSide::Top,
+                PanelSide::Bottom,
+            ],
+            Self::TopBottomPanels => [
+                PanelSide::Top,
+                PanelSide::Bottom,
+                PanelSide::Left,
+                PanelSide::Right,
+            ],
+        }
+    }
+}
+
+/// 可折叠工作区：统一管理四个方向的可折叠面板和中央 Dock
+///
+/// 每个方向可以放置一个未设置面板 ID 的面板和任意多个设置了
+/// [`with_panel_id`](CollapsibleDockPanel::with_panel_id) 的面板。同一方向的面板
+/// 按添加顺序从窗口边缘向内排列，相邻面板之间的边缘可以拖动调整尺寸。
+///
+/// 同一方向的面板只能并排排列，不能在一列（或一行）内上下（左右）分割；
+/// 需要分割时可以在面板的 `DockState` 中分割标签页区域。
+pub struct CollapsibleWorkspace<Tab: TabViewer> {
+    /// 工作区 ID，用于持久化
+    id: Id,
+    /// 左侧面板，从窗口边缘向内排列
+    left: Vec<CollapsibleDockPanel<Tab>>,
+    /// 右侧面板
+    right: Vec<CollapsibleDockPanel<Tab>>,
+    /// 顶部面板
+    top: Vec<CollapsibleDockPanel<Tab>>,
+    /// 底部面板
+    bottom: Vec<CollapsibleDockPanel<Tab>>,
+    /// 中央 Dock 状态
+    central: DockState<Tab::Tab>,
+    /// 窗口角落归属
+    corners: CornerOwnership,
+    /// 中央面板框架样式
+    central_frame: Option<Frame>,
+    /// 中央区域的最小尺寸，空间不足时按优先级自动折叠面板
+    min_central_size: Option<Vec2>,
+    /// 状态是否已加载
+    state_loaded: bool,
+    /// 按保存顺序排列的命名布局预设
+    presets: Vec<(String, LayoutPreset<Tab::Tab>)>,
+    /// 最近保存或切换到的预设
+    active_preset: Option<String>,
+    /// 正在跨面板拖动的标签页
+    tab_drag: Option<TabDrag>,
+    /// 在中央区域标签栏中按下的标签页
+    central_pressed_tab: PressedTab,
+}
+
+impl<Tab: TabViewer> CollapsibleWorkspace<Tab> {
+    /// 创建新的工作区
+    pub fn new(id: Id, central: DockState<Tab::Tab>) -> Self {
+        Self {
+            id,
+            left: Vec::new(),
+            right: Vec::new(),
+            top: Vec::new(),
+            bottom: Vec::new(),
+            central,
+            corners: CornerOwnership::default(),
+            central_frame: None,
+            min_central_size: None,
+            state_loaded: false,
+            presets: Vec::new(),
+            active_preset: None,
+            tab_drag: None,
+            central_pressed_tab: PressedTab::default(),
+        }
+    }
+
+    /// 添加面板，按面板方向放置（替换相同的已有面板，见 [`set_panel`](Self::set_panel)）
+    pub fn with_panel(mut self, panel: CollapsibleDockPanel<Tab>) -> Self {
+        self.set_panel(panel);
+        self
+    }
+
+    /// 设置窗口角落归属
+    pub fn with_corners(mut self, corners: CornerOwnership) -> Self {
+        self.corners = corners;
+        self
+    }
+
+    /// 设置中央面板框架
+    pub fn with_central_frame(mut self, frame: Frame) -> Self {
+        self.central_frame = Some(frame);
+        self
+    }
+
+    /// 为中央区域保留最小尺寸
+    ///
+    /// 左右（或上下）展开面板的尺寸、折叠面板的折叠栏加上中央区域超过窗口时，按
+    /// [`with_collapse_priority`](CollapsibleDockPanel::with_collapse_priority)
+    /// 从低到高自动折叠面板；窗口变大后恢复展开。
+    pub fn with_min_central_size(mut self, size: Vec2) -> Self {
+        self.min_central_size = Some(size);
+        self
+    }
+
+    /// 放置面板，返回被替换的面板
+    ///
+    /// 设置了面板 ID 的面板替换 ID 相同的面板，否则替换同方向未设置 ID 的面板；
+    /// 没有可替换的面板时添加到该方向的最内侧。
+    ///
+    /// 工作区中面板的状态只由工作区保存到 egui 内存，被替换或移除的面板恢复单独保存。
+    pub fn set_panel(
+        &mut self,
+        mut panel: CollapsibleDockPanel<Tab>,
+    ) -> Option<CollapsibleDockPanel<Tab>> {
+        panel.set_persist_state(false);
+        let side = panel.side();
+        let existing = self.find_panel(|existing| match panel.panel_id() {
+            Some(id) => existing.panel_id() == Some(id),
+            None => existing.panel_id().is_none() && existing.side() == side,
+        });
+        let replaced = match existing {
+            Some((slot, index)) if slot == side => {
+                Some(std::mem::replace(&mut self.slot_mut(slot)[index], panel))
+            }
+            Some((slot, index)) => {
+                let replaced = self.slot_mut(slot).remove(index);
+                self.slot_mut(side).push(panel);
+                Some(replaced)
+            }
+            None => {
+                self.slot_mut(side).push(panel);
+                None
+            }
+        };
+        replaced.map(release_panel)
+    }
+
+    /// 移除指定方向最外侧的面板
+    pub fn remove_panel(&mut self, side: PanelSide) -> Option<CollapsibleDockPanel<Tab>> {
+        let slot = self.slot_mut(side);
+        (!slot.is_empty()).then(|| release_panel(slot.remove(0)))
+    }
+
+    /// 移除指定面板 ID 的面板
+    pub fn remove_panel_by_id(&mut self, id: &str) -> Option<CollapsibleDockPanel<Tab>> {
+        let (side, index) = self.find_panel(|panel| panel.panel_id() == Some(id))?;
+        Some(release_panel(self.slot_mut(side).remove(index)))
+    }
+
+    /// 获取指定方向最外侧的面板
+    pub fn panel(&self, side: PanelSide) -> Option<&CollapsibleDockPanel<Tab>> {
+        self.panels_on(side).first()
+    }
+
+    /// 获取指定方向最外侧的可变面板
+    pub fn panel_mut(&mut self, side: PanelSide) -> Option<&mut CollapsibleDockPanel<Tab>> {
+        self.slot_mut(side).first_mut()
+    }
+
+    /// 获取指定方向的所有面板，从窗口边缘向内排列
+    pub fn panels_on(&self, side: PanelSide) -> &[CollapsibleDockPanel<Tab>] {
+        match side {
+            PanelSide::Left => &self.left,
+            PanelSide::Right => &self.right,
+            PanelSide::Top => &self.top,
+            PanelSide::Bottom => &self.bottom,
+        }
+    }
+
+    /// 获取指定面板 ID 的面板
+    pub fn panel_by_id(&self, id: &str) -> Option<&CollapsibleDockPanel<Tab>> {
+        let (side, index) = self.find_panel(|panel| panel.panel_id() == Some(id))?;
+        self.panels_on(side).get(index)
+    }
+
+    /// 获取指定面板 ID 的可变面板
+    pub fn panel_by_id_mut(&mut self, id: &str) -> Option<&mut CollapsibleDockPanel<Tab>> {
+        let (side, index) = self.find_panel(|panel| panel.panel_id() == Some(id))?;
+        self.slot_mut(side).get_mut(index)
+    }
+
+    /// 获取中央 Dock 状态
+    pub fn central_dock_state(&self) -> &DockState<Tab::Tab> {
+        &self.central
+    }
+
+    /// 获取可变的中央 Dock 状态
+    pub fn central_dock_state_mut(&mut self) -> &mut DockState<Tab::Tab> {
+        &mut self.central
+    }
+
+    /// 获取指定位置的 Dock 状态
+    pub fn dock_state(&self, target: &DockTarget) -> Option<&DockState<Tab::Tab>> {
+        match target {
+            DockTarget::Central => Some(&self.central),
+            _ => {
+                let (side, index) = self.find_panel(|panel| panel.dock_target() == *target)?;
+                Some(self.panels_on(side)[index].dock_state())
+            }
+        }
+    }
+
+    /// 获取指定位置的可变 Dock 状态
+    pub fn dock_state_mut(&mut self, target: &DockTarget) -> Option<&mut DockState<Tab::Tab>> {
+        match target {
+            DockTarget::Central => Some(&mut self.central),
+            _ => {
+                let (side, index) = self.find_panel(|panel| panel.dock_target() == *target)?;
+                Some(self.slot_mut(side)[index].dock_state_mut())
+            }
+        }
+    }
+
+    /// 获取窗口角落归属
+    pub fn corners(&self) -> CornerOwnership {
+        self.corners
+    }
+
+    /// 设置窗口角落归属
+    pub fn set_corners(&mut self, corners: CornerOwnership) {
+        self.corners = corners;
+    }
+
+    /// 切换指定方向最外侧面板的折叠状态
+    pub fn toggle(&mut self, side: PanelSide) {
+        if let Some(panel) = self.panel_mut(side) {
+            panel.toggle();
+        }
+    }
+
+    /// 收集所有面板的状态
+    pub fn state(&self) -> CollapsibleDockState {
+        let mut state = CollapsibleDockState::new();
+        for side in self.corners.render_order() {
+            for panel in self.panels_on(side) {
+                panel.write_state(&mut state);
+            }
+        }
+        state
+    }
+
+    /// 恢复所有面板的状态，移动过的面板回到保存时的方向
+    pub fn restore_state(&mut self, state: &CollapsibleDockState) {
+        for side in self.corners.render_order() {
+            for panel in self.slot_mut(side) {
+                panel.restore_state(state);
+            }
+        }
+        self.relocate_moved_panels();
+        self.state_loaded = true;
+    }
+
+    /// 按顺序显示所有面板和中央 Dock，返回本帧各面板产生的事件
+    ///
+    /// 事件附带面板所在的方向和面板 ID（未设置时为 `None`）。
+    pub fn show(&mut self, ctx: &Context, tab_viewer: &mut Tab) -> Vec<WorkspaceEvent> {
+        self.show_areas(ctx, tab_viewer).0
+    }
+
+    /// 显示所有面板和中央 Dock，返回事件和可以接收标签页的区域
+    fn show_areas(
+        &mut self,
+        ctx: &Context,
+        tab_viewer: &mut Tab,
+    ) -> (Vec<WorkspaceEvent>, Vec<DropZone>) {
+        // 只在第一次调用时从内存加载整体状态
+        if !self.state_loaded {
+            if let Some(state) = CollapsibleDockState::try_load_from_memory(ctx, self.id) {
+                self.restore_state(&state);
+            }
+            self.state_loaded = true;
+        }
+
+        self.relocate_moved_panels();
+        self.fit_panels_to_window(ctx.content_rect().size());
+
+        // 先渲染的面板拥有窗口角落
+        let mut events = self.handle_shortcuts(ctx);
+        let mut zones = Vec::new();
+        for side in self.corners.render_order() {
+            for panel in self.slot_mut(side).iter_mut() {
+                let (response, panel_events) = panel.show_with_events(ctx, tab_viewer);
+                events.extend(
+                    panel_events
+                        .into_iter()
+                        .map(|event| (side, panel.panel_id().map(str::to_owned), event)),
+                );
+                if let Some(response) = response {
+                    zones.push(DropZone {
+                        target: panel.dock_target(),
+                        rect: response.rect,
+                        pressed_tab: panel.pressed_tab(ctx),
+                    });
+                }
+            }
+        }
+
+        // 右键菜单可能在本帧移动了面板
+        self.relocate_moved_panels();
+
+        // 中央区域
+        let frame = self
+            .central_frame
+            .unwrap_or_else(|| Frame::central_panel(ctx.style().as_ref()));
+        let mut tracker = TabButtonTracker::new(tab_viewer);
+        let central = egui::CentralPanel::default().frame(frame).show(ctx, |ui| {
+            ui.push_id((self.id, "central_dock_area"), |ui| {
+                DockArea::new(&mut self.central)
+                    .id(self.id.with("central_dock_area_unique"))
+                    .style(egui_dock::Style::from_egui(ui.ctx().style().as_ref()))
+                    .show_leaf_collapse_buttons(false)
+                    .show_inside(ui, &mut tracker);
+            });
+        });
+        self.central_pressed_tab
+            .update(ctx, &self.central, tracker.pressed);
+        zones.push(DropZone {
+            target: DockTarget::Central,
+            rect: central.response.rect,
+            pressed_tab: self.central_pressed_tab.get(ctx),
+        });
+
+        // 保存整体状态
+        self.state().save_to_memory(ctx, self.id);
+
+        (events, zones)
+    }
+
+    /// 按具体程度统一处理所有面板的快捷键
+    ///
+    /// egui 匹配时忽略多余的 Shift/Alt，如果由各面板依次处理，先渲染面板的 `Ctrl+B`
+    /// 会吞掉后渲染面板的 `Ctrl+Alt+B`。多个面板绑定同一快捷键时都不执行并产生冲突事件。
+    fn handle_shortcuts(&mut self, ctx: &Context) -> Vec<WorkspaceEvent> {
+        let order = self.corners.render_order();
+        let shortcuts = shortcuts_by_priority(
+            order
+                .iter()
+                .flat_map(|side| self.panels_on(*side))
+                .map(|panel| panel.keymap()),
+        );
+
+        let mut events = Vec::new();
+        for shortcut in shortcuts {
+            let pressed =
+                ctx.input_mut(|input| input.consume_shortcut(&shortcut.to_keyboard_shortcut()));
+            if !pressed {
+                continue;
+            }
+
+            let bound: Vec<(PanelSide, usize)> = order
+                .into_iter()
+                .flat_map(|side| {
+                    self.panels_on(side)
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, panel)| panel.keymap().actions(shortcut).next().is_some())
+                        .map(move |(index, _)| (side, index))
+                })
+                .collect();
+            let handled = match bound.as_slice() {
+                [(side, index)] => self.slot_mut(*side)[*index].run_shortcut(shortcut),
+                _ => false,
+            };
+            if !handled {
+                events.extend(bound.into_iter().map(|(side, index)| {
+                    let panel_id = self.panels_on(side)[index].panel_id().map(str::to_owned);
+                    (side, panel_id, PanelEvent::ShortcutConflict(shortcut))
+                }));
+            }
+        }
+        events
+    }
+
+    /// 检查所有面板快捷键之间的冲突
+    ///
+    /// 包括按下一个快捷键时同时匹配另一个快捷键的情况，见 [`KeymapConflict`]。
+    pub fn keymap_conflicts(&self) -> Vec<KeymapConflict> {
+        find_conflicts(self.corners.render_order().into_iter().flat_map(|side| {
+            self.panels_on(side)
+                .iter()
+                .map(move |panel| (side, panel.keymap()))
+        }))
+    }
+
+    /// 按保存顺序列出预设名称
+    pub fn preset_names(&self) -> impl Iterator<Item = &str> {
+        self.presets.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// 获取指定名称的预设
+    pub fn preset(&self, name: &str) -> Option<&LayoutPreset<Tab::Tab>> {
+        self.presets
+            .iter()
+            .find(|(preset_name, _)| preset_name == name)
+            .map(|(_, preset)| preset)
+    }
+
+    /// 获取最近保存或切换到的预设名称
+    pub fn active_preset(&self) -> Option<&str> {
+        self.active_preset.as_deref()
+    }
+
+    /// 删除指定名称的预设
+    pub fn delete_preset(&mut self, name: &str) -> Option<LayoutPreset<Tab::Tab>> {
+        let index = self
+            .presets
+            .iter()
+            .position(|(preset_name, _)| preset_name == name)?;
+        if self.active_preset.as_deref() == Some(name) {
+            self.active_preset = None;
+        }
+        Some(self.presets.remove(index).1)
+    }
+
+    /// 根据中央区域的最小尺寸决定哪些面板需要自动折叠
+    fn fit_panels_to_window(&mut self, window_size: Vec2) {
+        let Some(min_central) = self.min_central_size else {
+            return;
+        };
+        self.fit_panels(
+            [PanelSide::Left, PanelSide::Right],
+            window_size.x - min_central.x,
+        );
+        self.fit_panels(
+            [PanelSide::Top, PanelSide::Bottom],
+            window_size.y - min_central.y,
+        );
+    }
+
+    /// 面板（折叠的面板按折叠栏计算）的总尺寸超过 `available` 时，从优先级最低的面板开始折叠
+    fn fit_panels(&mut self, sides: [PanelSide; 2], available: f32) {
+        let mut expanded: Vec<(i32, PanelSide, usize)> = Vec::new();
+        let mut total = 0.0;
+        for side in sides {
+            for (index, panel) in self.slot_mut(side).iter_mut().enumerate() {
+                panel.set_space_collapse(false);
+                if panel.wants_expanded() {
+                    expanded.push((panel.collapse_priority(), side, index));
+                    total += panel.get_size();
+                } else {
+                    total += panel.collapsed_size();
+                }
+            }
+        }
+
+        // 优先级相同时先折叠靠内侧的面板
+        expanded.sort_by_key(|&(priority, _, index)| (priority, std::cmp::Reverse(index)));
+        for (_, side, index) in expanded {
+            if total <= available {
+                break;
+            }
+            let panel = &mut self.slot_mut(side)[index];
+            panel.set_space_collapse(true);
+            total -= panel.get_size() - panel.collapsed_size();
+        }
+    }
+
+    /// 查找满足条件的面板，返回所在方向和序号
+    fn find_panel(
+        &self,
+        predicate: impl Fn(&CollapsibleDockPanel<Tab>) -> bool,
+    ) -> Option<(PanelSide, usize)> {
+        self.corners.render_order().into_iter().find_map(|side| {
+            self.panels_on(side)
+                .iter()
+                .position(&predicate)
+                .map(|index| (side, index))
+        })
+    }
+
+    /// 把通过 `set_side` 移动的面板放到新方向的槽位
+    ///
+    /// 每个方向只能有一个未设置面板 ID 的面板，新方向已有这样的面板时两者交换位置；
+    /// 设置了面板 ID 的面板添加到新方向的最内侧。
+    fn relocate_moved_panels(&mut self) {
+        for side in self.corners.render_order() {
+            let mut index = 0;
+            while index < self.panels_on(side).len() {
+                let moved_to = self.panels_on(side)[index].side();
+                if moved_to == side {
+                    index += 1;
+                    continue;
+                }
+
+                let panel = self.slot_mut(side).remove(index);
+                let target = self.slot_mut(moved_to);
+                let occupant = target
+                    .iter()
+                    .position(|other| other.panel_id().is_none() && other.side() == moved_to)
+                    .filter(|_| panel.panel_id().is_none());
+                match occupant {
+                    Some(occupant) => {
+                        let mut displaced = std::mem::replace(&mut target[occupant], panel);
+                        displaced.set_side(side);
+                        self.slot_mut(side).insert(index, displaced);
+                        index += 1;
+                    }
+                    None => target.push(panel),
+                }
+            }
+        }
+    }
+
+    /// 获取指定方向的面板槽位
+    fn slot_mut(&mut self, side: PanelSide) -> &mut Vec<CollapsibleDockPanel<Tab>> {
+        match side {
+            PanelSide::Left => &mut self.left,
+            PanelSide::Right => &mut self.right,
+            PanelSide::Top => &mut self.top,
+            PanelSide::Bottom => &mut self.bottom,
+        }
+    }
+}
+
+impl<Tab: TabDragViewer> CollapsibleWorkspace<Tab>
+where
+    Tab::Tab: Clone + PartialEq,
+{
+    /// 显示工作区，并允许把标签页拖放到其他面板（包括折叠栏）或中央区域
+    ///
+    /// 拖动标签栏中的标签页时，其他面板会显示放置区域；在放置区域松开后
+    /// 标签页移动到对应的 `DockState`，并调用 [`TabDragViewer::on_tab_moved`]。
+    /// 在标签页原来所在的面板内或窗口（例如 egui_dock 的浮动窗口）上方松开时，
+    /// 仍由 egui_dock 处理。
+    pub fn show_with_tab_drag(
+        &mut self,
+        ctx: &Context,
+        tab_viewer: &mut Tab,
+    ) -> Vec<WorkspaceEvent> {
+        let (events, zones) = self.show_areas(ctx, tab_viewer);
+        self.update_tab_drag(ctx, tab_viewer, &zones);
+        events
+    }
+
+    /// 把标签页从一个 `DockState` 移动到另一个，找不到标签页或目标时返回 `false`
+    ///
+    /// 标签页添加到目标的焦点区域，目标面板保持当前的折叠状态。
+    pub fn move_tab(
+        &mut self,
+        tab_viewer: &mut Tab,
+        tab: &Tab::Tab,
+        from: DockTarget,
+        to: DockTarget,
+    ) -> bool {
+        let Some(path) = self
+            .dock_state(&from)
+            .and_then(|source| source.find_tab(tab))
+        else {
+            return false;
+        };
+        self.move_tab_at(tab_viewer, from, path, to)
+    }
+
+    /// 把 `from` 中位于 `path` 的标签页移动到 `to`
+    fn move_tab_at(
+        &mut self,
+        tab_viewer: &mut Tab,
+        from: DockTarget,
+        path: TabPath,
+        to: DockTarget,
+    ) -> bool {
+        if from == to || self.dock_state(&to).is_none() {
+            return false;
+        }
+        let Some(mut tab) = self
+            .dock_state_mut(&from)
+            .and_then(|source| source.remove_tab(path))
+        else {
+            return false;
+        };
+
+        tab_viewer.on_tab_moved(&mut tab, from, to.clone());
+        if let Some(target) = self.dock_state_mut(&to) {
+            target.push_to_focused_leaf(tab);
+        }
+        true
+    }
+
+    /// 跟踪标签页拖动，绘制放置区域并在松开时移动标签页
+    ///
+    /// egui_dock 根据上一帧的指针位置决定松开时的放置位置，并且只处理自己的标签栏、
+    /// 节点和浮动窗口。这里同样使用上一帧高亮的区域，并跳过被窗口遮挡的区域，
+    /// 因此同一次松开只会由其中一方移动标签页。
+    fn update_tab_drag(&mut self, ctx: &Context, tab_viewer: &mut Tab, zones: &[DropZone]) {
+        let (pointer_down, dragging, pointer_pos) = ctx.input(|input| {
+            (
+                input.pointer.primary_down(),
+                input.pointer.is_decidedly_dragging(),
+                input.pointer.latest_pos(),
+            )
+        });
+
+        // 指针按在标签按钮上之后开始拖动，拖动的就是这个标签页
+        if self.tab_drag.is_none() && dragging && ctx.dragged_id().is_some() {
+            self.tab_drag = zones.iter().find_map(|zone| {
+                Some(TabDrag {
+                    from: zone.target.clone(),
+                    path: zone.pressed_tab?,
+                    hovered: None,
+                })
+            });
+        }
+        let Some(drag) = &mut self.tab_drag else {
+            return;
+        };
+
+        if pointer_down {
+            drag.hovered = pointer_pos.and_then(|pos| {
+                zones
+                    .iter()
+                    .find(|zone| zone.target != drag.from && zone.rect.contains(pos))
+                    .filter(|_| !is_over_window(ctx, pos))
+                    .map(|zone| zone.target.clone())
+            });
+            paint_drop_zones(ctx, zones, &drag.from, drag.hovered.as_ref());
+            return;
+        }
+
+        // 松开指针：放在其他区域时移动标签页，否则交给 egui_dock 处理
+        if let Some(drag) = self.tab_drag.take() {
+            if let Some(to) = drag.hovered {
+                self.move_tab_at(tab_viewer, drag.from, drag.path, to);
+            }
+        }
+    }
+}
+
+/// 离开工作区的面板恢复单独保存状态
+fn release_panel<Tab: TabViewer>(
+    mut panel: CollapsibleDockPanel<Tab>,
+) -> CollapsibleDockPanel<Tab> {
+    panel.set_persist_state(true);
+    panel
+}
+
+/// 指针是否位于窗口（例如 egui_dock 的浮动窗口）上方，此时由窗口处理放置
+fn is_over_window(ctx: &Context, pos: Pos2) -> bool {
+    ctx.layer_id_at(pos)
+        .is_some_and(|layer| layer.order != Order::Background)
+}
+
+impl<Tab: TabViewer> CollapsibleWorkspace<Tab>
+where
+    Tab::Tab: Clone,
+{
+    /// 捕获当前的完整布局
+    pub fn capture_preset(&self) -> LayoutPreset<Tab::Tab> {
+        let mut preset = LayoutPreset {
+            state: self.state(),
+            active_buttons: Default::default(),
+            panel_docks: Default::default(),
+            keyed_active_buttons: Default::default(),
+            keyed_panel_docks: Default::default(),
+            central: self.central.clone(),
+        };
+        for side in self.corners.render_order() {
+            for panel in self.panels_on(side) {
+                let active_button = panel.get_active_button();
+                let dock_state = panel.dock_state().clone();
+                match panel.panel_id() {
+                    Some(id) => {
+                        preset
+                            .keyed_active_buttons
+                            .insert(id.to_owned(), active_button);
+                        preset.keyed_panel_docks.insert(id.to_owned(), dock_state);
+                    }
+                    None => {
+                        preset.active_buttons.insert(side, active_button);
+                        preset.panel_docks.insert(side, dock_state);
+                    }
+                }
+            }
+        }
+        preset
+    }
+
+    /// 将当前布局保存为命名预设，同名预设会被覆盖并保持原有位置
+    pub fn save_preset(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        let preset = self.capture_preset();
+        match self
+            .presets
+            .iter_mut()
+            .find(|(preset_name, _)| *preset_name == name)
+        {
+            Some((_, existing)) => *existing = preset,
+            None => self.presets.push((name.clone(), preset)),
+        }
+        self.active_preset = Some(name);
+    }
+
+    /// 切换到指定名称的预设，预设不存在时返回 `false`
+    pub fn switch_to_preset(&mut self, name: &str) -> bool {
+        let Some(preset) = self.preset(name).cloned() else {
+            return false;
+        };
+        self.apply_preset(&preset);
+        self.active_preset = Some(name.to_owned());
+        true
+    }
+
+    /// 应用布局预设，面板以动画方式过渡到新的方向、折叠状态和尺寸
+    pub fn apply_preset(&mut self, preset: &LayoutPreset<Tab::Tab>) {
+        self.central = preset.central.clone();
+        for side in self.corners.render_order() {
+            for panel in self.slot_mut(side) {
+                panel.animate_to_state(&preset.state);
+            }
+        }
+        // 预设中未设置面板 ID 的面板的 Dock 状态和激活按钮按面板在预设中的方向保存
+        self.relocate_moved_panels();
+        for side in self.corners.render_order() {
+            for panel in self.slot_mut(side) {
+                let (dock_state, active_button) = match panel.panel_id() {
+                    Some(id) => (
+                        preset.keyed_panel_docks.get(id),
+                        preset.keyed_active_buttons.get(id),
+                    ),
+                    None => (
+                        preset.panel_docks.get(&side),
+                        preset.active_buttons.get(&side),
+                    ),
+                };
+                let active_button = active_button.copied().flatten();
+                if let Some(dock_state) = dock_state {
+                    panel.set_dock_state(dock_state.clone());
+                }
+                if let Some(index) = active_button {
+                    panel.set_active_button(index);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,462 @@
+//! Fixtures shared by the integration tests.
+//!
+//! Every test binary pulls this in with `mod common;` and uses only part of
+//! it, hence the blanket `dead_code` allowance.
+
+#![allow(dead_code)]
+
+use egui::{Context, Event, Id, Key, Modifiers, Pos2, RawInput, Rect, Ui, Vec2, WidgetText};
+use egui_collapsible_dock::{
+    CollapsibleButton, CollapsibleDockPanel, CollapsibleToolbar, CollapsibleWorkspace, DockTarget,
+    PanelEvent, PanelSide, TabDragViewer, ToolbarTabViewer, WorkspaceEvent,
+};
+use egui_dock::{DockState, TabViewer};
+
+pub const SCREEN_SIZE: Vec2 = Vec2::new(1280.0, 800.0);
+
+pub const ALL_SIDES: [PanelSide; 4] = [
+    PanelSide::Left,
+    PanelSide::Right,
+    PanelSide::Top,
+    PanelSide::Bottom,
+];
+
+/// Tab viewer for `String` tabs that records moves reported by the workspace.
+#[derive(Default)]
+pub struct TestViewer {
+    /// Moves reported through `on_tab_moved`, in order.
+    pub moves: Vec<(String, DockTarget, DockTarget)>,
+    /// Tab contents shown through `ui`, in order.
+    pub shown: Vec<ShownTab>,
+    /// Options of a combo box shown under each tab's label, if any.
+    pub combo_box: Vec<&'static str>,
+}
+
+/// One call of `TabViewer::ui`.
+#[derive(Clone, Debug)]
+pub struct ShownTab {
+    pub tab: String,
+    /// `max_rect` of the ui the content was given.
+    pub rect: Rect,
+    pub opacity: f32,
+}
+
+impl TestViewer {
+    /// Rect the content of `tab` was given since `shown` was last cleared.
+    pub fn content_rect(&self, tab: &str) -> Rect {
+        self.shown
+            .iter()
+            .find(|shown| shown.tab == tab)
+            .unwrap_or_else(|| panic!("{tab} was not shown: {:?}", self.shown))
+            .rect
+    }
+}
+
+impl TabViewer for TestViewer {
+    type Tab = String;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+        tab.as_str().into()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        self.shown.push(ShownTab {
+            tab: tab.clone(),
+            rect: ui.max_rect(),
+            opacity: ui.opacity(),
+        });
+        ui.label(tab.as_str());
+        if !self.combo_box.is_empty() {
+            egui::ComboBox::from_id_salt(("combo_box", tab.as_str()))
+                .selected_text("Pick")
+                .show_ui(ui, |ui| {
+                    for option in &self.combo_box {
+                        let _ = ui.selectable_label(false, *option);
+                    }
+                });
+        }
+    }
+}
+
+impl TabDragViewer for TestViewer {
+    fn on_tab_moved(&mut self, tab: &mut Self::Tab, from: DockTarget, to: DockTarget) {
+        self.moves.push((tab.clone(), from, to));
+    }
+}
+
+pub type TestPanel = CollapsibleDockPanel<TestViewer>;
+pub type TestWorkspace = CollapsibleWorkspace<TestViewer>;
+
+/// Panel with a single "Files" tab and "Files"/"Search" strip buttons.
+pub fn test_panel(side: PanelSide, id: Id) -> TestPanel {
+    CollapsibleDockPanel::new(side, id)
+        .with_dock_state(DockState::new(vec!["Files".to_owned()]))
+        .add_button(CollapsibleButton::new("Files"))
+        .add_button(CollapsibleButton::new("Search"))
+}
+
+/// Panel named `name` holding one tab of the same name.
+pub fn panel(side: PanelSide, name: &str) -> TestPanel {
+    panel_with_tabs(side, name, &[name])
+}
+
+/// Panel named `name` holding `tabs`, with "Files"/"Search" strip buttons.
+pub fn panel_with_tabs(side: PanelSide, name: &str, tabs: &[&str]) -> TestPanel {
+    CollapsibleDockPanel::new(side, Id::new(name))
+        .with_dock_state(DockState::new(
+            tabs.iter().map(|tab| tab.to_string()).collect(),
+        ))
+        .add_button(CollapsibleButton::new("Files"))
+        .add_button(CollapsibleButton::new("Search"))
+}
+
+/// Tab titles of `dock_state`, in order.
+pub fn tab_names(dock_state: &DockState<String>) -> Vec<String> {
+    dock_state
+        .iter_all_tabs()
+        .map(|(_, tab)| tab.clone())
+        .collect()
+}
+
+/// Something the harness draws once per frame.
+pub trait Scene {
+    /// What the scene reports from one frame.
+    type Event;
+
+    /// Shows the scene, returning its main rect (if any) and its events.
+    fn show(&mut self, ctx: &Context) -> (Option<Rect>, Vec<Self::Event>);
+}
+
+impl Scene for TestPanel {
+    type Event = PanelEvent;
+
+    fn show(&mut self, ctx: &Context) -> (Option<Rect>, Vec<PanelEvent>) {
+        let (response, events) = self.show_with_events(ctx, &mut TestViewer::default());
+        egui::CentralPanel::default().show(ctx, |_ui| {});
+        (response.map(|response| response.rect), events)
+    }
+}
+
+/// Panel shown with a viewer that keeps what the last frame showed.
+pub struct PanelScene {
+    pub panel: TestPanel,
+    pub viewer: TestViewer,
+}
+
+impl PanelScene {
+    pub fn new(panel: TestPanel) -> Self {
+        Self {
+            panel,
+            viewer: TestViewer::default(),
+        }
+    }
+}
+
+impl Scene for PanelScene {
+    type Event = PanelEvent;
+
+    fn show(&mut self, ctx: &Context) -> (Option<Rect>, Vec<PanelEvent>) {
+        self.viewer.shown.clear();
+        let (response, events) = self.panel.show_with_events(ctx, &mut self.viewer);
+        egui::CentralPanel::default().show(ctx, |_ui| {});
+        (response.map(|response| response.rect), events)
+    }
+}
+
+impl Scene for TestWorkspace {
+    type Event = WorkspaceEvent;
+
+    fn show(&mut self, ctx: &Context) -> (Option<Rect>, Vec<WorkspaceEvent>) {
+        (
+            None,
+            CollapsibleWorkspace::show(self, ctx, &mut TestViewer::default()),
+        )
+    }
+}
+
+/// Workspace shown through `show` with a viewer that keeps what the last
+/// frame showed.
+pub struct WorkspaceScene {
+    pub workspace: TestWorkspace,
+    pub viewer: TestViewer,
+}
+
+impl WorkspaceScene {
+    pub fn new(workspace: TestWorkspace) -> Self {
+        Self {
+            workspace,
+            viewer: TestViewer::default(),
+        }
+    }
+
+    /// Rect the content of `tab` was given in the last frame.
+    pub fn content_rect(&self, tab: &str) -> Rect {
+        self.viewer.content_rect(tab)
+    }
+}
+
+impl Scene for WorkspaceScene {
+    type Event = WorkspaceEvent;
+
+    fn show(&mut self, ctx: &Context) -> (Option<Rect>, Vec<WorkspaceEvent>) {
+        self.viewer.shown.clear();
+        (None, self.workspace.show(ctx, &mut self.viewer))
+    }
+}
+
+/// Workspace shown through `show_with_tab_drag` with a persistent viewer
+/// that keeps what the last frame showed.
+pub struct TabDragScene {
+    pub workspace: TestWorkspace,
+    pub viewer: TestViewer,
+}
+
+impl TabDragScene {
+    pub fn new(workspace: TestWorkspace) -> Self {
+        Self {
+            workspace,
+            viewer: TestViewer::default(),
+        }
+    }
+}
+
+impl Scene for TabDragScene {
+    type Event = WorkspaceEvent;
+
+    fn show(&mut self, ctx: &Context) -> (Option<Rect>, Vec<WorkspaceEvent>) {
+        self.viewer.shown.clear();
+        (
+            None,
+            self.workspace.show_with_tab_drag(ctx, &mut self.viewer),
+        )
+    }
+}
+
+/// Toolbar viewer for `String` tabs that records close requests.
+#[derive(Default)]
+pub struct ToolbarViewer {
+    /// Whether `on_close` vetoes closing.
+    pub veto_close: bool,
+    /// Tabs passed to `on_close`, in order.
+    pub close_requests: Vec<String>,
+}
+
+impl ToolbarTabViewer for ToolbarViewer {
+    type Tab = String;
+
+    fn title(&self, tab: &Self::Tab) -> String {
+        tab.clone()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &Self::Tab) {
+        ui.label(tab.as_str());
+    }
+
+    fn closable(&self, _tab: &Self::Tab) -> bool {
+        true
+    }
+
+    fn on_close(&mut self, tab: &Self::Tab) -> bool {
+        self.close_requests.push(tab.clone());
+        !self.veto_close
+    }
+}
+
+pub const TOOLBAR_ID: &str = "toolbar";
+
+/// Toolbar shown under `TOOLBAR_ID` with a persistent viewer.
+pub struct ToolbarScene {
+    pub toolbar: CollapsibleToolbar<String>,
+    pub viewer: ToolbarViewer,
+}
+
+impl ToolbarScene {
+    pub fn new(toolbar: CollapsibleToolbar<String>) -> Self {
+        Self {
+            toolbar,
+            viewer: ToolbarViewer::default(),
+        }
+    }
+}
+
+impl Scene for ToolbarScene {
+    type Event = ();
+
+    fn show(&mut self, ctx: &Context) -> (Option<Rect>, Vec<()>) {
+        let response = self
+            .toolbar
+            .show(ctx, Id::new(TOOLBAR_ID), &mut self.viewer);
+        (response.map(|response| response.rect), Vec::new())
+    }
+}
+
+/// Drives one `Context` frame by frame with a steadily advancing clock.
+pub struct Harness {
+    pub ctx: Context,
+    pub time: f64,
+    pub screen_size: Vec2,
+}
+
+impl Harness {
+    pub fn new() -> Self {
+        Self {
+            ctx: Context::default(),
+            time: 0.0,
+            screen_size: SCREEN_SIZE,
+        }
+    }
+
+    /// Input for the next frame; advances the clock.
+    pub fn raw_input(&mut self, events: Vec<Event>) -> RawInput {
+        let raw_input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, self.screen_size)),
+            time: Some(self.time),
+            predicted_dt: 1.0 / 60.0,
+            events,
+            ..Default::default()
+        };
+        self.time += 1.0 / 60.0;
+        raw_input
+    }
+
+    /// Runs a single frame, returning the scene rect and the events it emitted.
+    pub fn frame<S: Scene>(
+        &mut self,
+        scene: &mut S,
+        events: Vec<Event>,
+    ) -> (Option<Rect>, Vec<S::Event>) {
+        let raw_input = self.raw_input(events);
+        let mut result = (None, Vec::new());
+        let _ = self.ctx.run(raw_input, |ctx| {
+            result = scene.show(ctx);
+        });
+        result
+    }
+
+    /// Runs enough frames for any running animation to finish.
+    pub fn settle<S: Scene>(&mut self, scene: &mut S) -> (Option<Rect>, Vec<S::Event>) {
+        let mut all_events = Vec::new();
+        let mut rect = None;
+        for _ in 0..40 {
+            let (frame_rect, events) = self.frame(scene, Vec::new());
+            rect = frame_rect;
+            all_events.extend(events);
+        }
+        (rect, all_events)
+    }
+
+    pub fn click<S: Scene>(&mut self, scene: &mut S, pos: Pos2) -> Vec<S::Event> {
+        let mut events = Vec::new();
+        events.extend(self.frame(scene, vec![Event::PointerMoved(pos)]).1);
+        events.extend(self.frame(scene, vec![pointer_button(pos, true)]).1);
+        events.extend(self.frame(scene, vec![pointer_button(pos, false)]).1);
+        events.extend(self.settle(scene).1);
+        events
+    }
+
+    /// Opens the context menu at `pos`.
+    pub fn right_click<S: Scene>(&mut self, scene: &mut S, pos: Pos2) {
+        let button = egui::PointerButton::Secondary;
+        self.frame(scene, vec![Event::PointerMoved(pos)]);
+        self.frame(scene, vec![pointer_event(pos, button, true)]);
+        self.frame(scene, vec![pointer_event(pos, button, false)]);
+        self.settle(scene);
+    }
+
+    pub fn drag<S: Scene>(&mut self, scene: &mut S, from: Pos2, to: Pos2) -> Vec<S::Event> {
+        let mut events = Vec::new();
+        events.extend(self.frame(scene, vec![Event::PointerMoved(from)]).1);
+        events.extend(self.frame(scene, vec![pointer_button(from, true)]).1);
+        for step in 1..=10 {
+            let pos = from + (to - from) * (step as f32 / 10.0);
+            events.extend(self.frame(scene, vec![Event::PointerMoved(pos)]).1);
+        }
+        events.extend(self.frame(scene, vec![pointer_button(to, false)]).1);
+        events.extend(self.settle(scene).1);
+        events
+    }
+
+    pub fn double_click<S: Scene>(&mut self, scene: &mut S, pos: Pos2) -> Vec<S::Event> {
+        let mut events = Vec::new();
+        events.extend(self.frame(scene, vec![Event::PointerMoved(pos)]).1);
+        for _ in 0..2 {
+            events.extend(self.frame(scene, vec![pointer_button(pos, true)]).1);
+            events.extend(self.frame(scene, vec![pointer_button(pos, false)]).1);
+        }
+        events.extend(self.settle(scene).1);
+        events
+    }
+
+    /// Presses and releases `key` with `modifiers`.
+    pub fn press<S: Scene>(
+        &mut self,
+        scene: &mut S,
+        modifiers: Modifiers,
+        key: Key,
+    ) -> Vec<S::Event> {
+        let mut events = self.frame(scene, key_press(modifiers, key)).1;
+        events.extend(self.settle(scene).1);
+        events
+    }
+
+    /// Texts painted in one frame of `scene`.
+    pub fn painted_texts<S: Scene>(&mut self, scene: &mut S) -> Vec<String> {
+        self.painted_text_rects(scene)
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect()
+    }
+
+    /// Texts painted in one frame of `scene`, with where they were painted.
+    pub fn painted_text_rects<S: Scene>(&mut self, scene: &mut S) -> Vec<(String, Rect)> {
+        let raw_input = self.raw_input(Vec::new());
+        let output = self.ctx.run(raw_input, |ctx| {
+            scene.show(ctx);
+        });
+        output
+            .shapes
+            .iter()
+            .filter_map(|clipped| match &clipped.shape {
+                egui::Shape::Text(text) => {
+                    Some((text.galley.text().to_owned(), text.visual_bounding_rect()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+pub fn key_press(modifiers: Modifiers, key: Key) -> Vec<Event> {
+    [true, false]
+        .into_iter()
+        .map(|pressed| Event::Key {
+            key,
+            physical_key: None,
+            pressed,
+            repeat: false,
+            modifiers,
+        })
+        .collect()
+}
+
+pub fn pointer_button(pos: Pos2, pressed: bool) -> Event {
+    pointer_event(pos, egui::PointerButton::Primary, pressed)
+}
+
+pub fn pointer_event(pos: Pos2, button: egui::PointerButton, pressed: bool) -> Event {
+    Event::PointerButton {
+        pos,
+        button,
+        pressed,
+        modifiers: Modifiers::NONE,
+    }
+}
+
+/// Drag-driven sizes go through egui's pixel rounding, so allow sub-pixel slack.
+#[track_caller]
+pub fn assert_close(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() < 1.0,
+        "expected {expected}, got {actual}"
+    );
+}
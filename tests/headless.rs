@@ -0,0 +1,1241 @@
+//! Headless tests driving `egui::Context::run` with synthetic input.
+//!
+//! No GPU or window is needed: every frame is fed a hand-built `RawInput`
+//! and the resulting panel rects and `PanelEvent`s are inspected.
+
+mod common;
+
+use common::{
+    assert_close, key_press, pointer_button, test_panel, Harness, PanelScene, ShownTab, TestPanel,
+    TestViewer, ALL_SIDES, SCREEN_SIZE,
+};
+use egui::{Event, Id, Key, Modifiers, Pos2, Rect, Vec2};
+use egui_collapsible_dock::{
+    Badge, BadgeSeverity, Breakpoint, CollapsibleButton, CollapsibleDockPanel,
+    CollapsibleDockState, Easing, ExpandMode, KeyAction, Keymap, Locale, PanelEvent, PanelSide,
+    Shortcut, StringKey, Strings, TransitionMode,
+};
+use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+use egui_phosphor::regular as phosphor;
+
+/// Collapsed strip thickness: 14px icon + 2 * 6px padding.
+const COLLAPSED_SIZE: f32 = 26.0;
+
+/// Panel extent along the axis it grows in.
+fn thickness(side: PanelSide, rect: Rect) -> f32 {
+    match side {
+        PanelSide::Left | PanelSide::Right => rect.width(),
+        PanelSide::Top | PanelSide::Bottom => rect.height(),
+    }
+}
+
+/// Middle of the panel edge that faces the central area (the resize separator).
+fn inner_edge(side: PanelSide, rect: Rect) -> Pos2 {
+    match side {
+        PanelSide::Left => rect.right_center(),
+        PanelSide::Right => rect.left_center(),
+        PanelSide::Top => rect.center_bottom(),
+        PanelSide::Bottom => rect.center_top(),
+    }
+}
+
+/// Offset that moves the inner edge outward, i.e. grows the panel.
+fn grow(side: PanelSide, amount: f32) -> Vec2 {
+    match side {
+        PanelSide::Left => Vec2::new(amount, 0.0),
+        PanelSide::Right => Vec2::new(-amount, 0.0),
+        PanelSide::Top => Vec2::new(0.0, amount),
+        PanelSide::Bottom => Vec2::new(0.0, -amount),
+    }
+}
+
+/// Center of the first activity button in a collapsed vertical strip.
+fn first_strip_button(rect: Rect) -> Pos2 {
+    strip_button(rect, 0)
+}
+
+/// Center of the `index`-th activity button in a collapsed vertical strip
+/// (20px buttons, 2px apart).
+fn strip_button(rect: Rect, index: usize) -> Pos2 {
+    Pos2::new(rect.center().x, rect.top() + 10.0 + 22.0 * index as f32)
+}
+
+#[test]
+fn panels_attach_to_their_side() {
+    for side in ALL_SIDES {
+        let mut harness = Harness::new();
+        let mut panel = test_panel(side, Id::new(("attach", side)));
+        let rect = harness.settle(&mut panel).0.expect("panel is shown");
+        let screen = Rect::from_min_size(Pos2::ZERO, SCREEN_SIZE);
+
+        match side {
+            PanelSide::Left => assert_eq!(rect.left(), screen.left()),
+            PanelSide::Right => assert_eq!(rect.right(), screen.right()),
+            PanelSide::Top => assert_eq!(rect.top(), screen.top()),
+            PanelSide::Bottom => assert_eq!(rect.bottom(), screen.bottom()),
+        }
+        assert_close(thickness(side, rect), panel.get_size());
+    }
+}
+
+#[test]
+fn collapsed_panels_shrink_to_strip() {
+    for side in ALL_SIDES {
+        let mut harness = Harness::new();
+        let mut panel = test_panel(side, Id::new(("strip", side)));
+        panel.set_collapsed(true);
+        let rect = harness.settle(&mut panel).0.expect("strip is shown");
+
+        assert_eq!(thickness(side, rect), COLLAPSED_SIZE, "{side:?}");
+        // Collapsing must not forget the expanded size.
+        assert_eq!(panel.get_size(), 300.0, "{side:?}");
+    }
+}
+
+#[test]
+fn collapsed_panel_without_buttons_is_hidden() {
+    let mut harness = Harness::new();
+    let mut panel: TestPanel = CollapsibleDockPanel::new(PanelSide::Left, Id::new("hidden"));
+    panel.set_collapsed(true);
+    assert!(harness.settle(&mut panel).0.is_none());
+}
+
+#[test]
+fn strip_button_expands_vertical_panels() {
+    for side in [PanelSide::Left, PanelSide::Right] {
+        let mut harness = Harness::new();
+        let mut panel = test_panel(side, Id::new(("strip_button", side)));
+        panel.set_collapsed(true);
+        let rect = harness.settle(&mut panel).0.unwrap();
+
+        let events = harness.click(&mut panel, first_strip_button(rect));
+
+        assert!(!panel.is_collapsed(), "{side:?}");
+        assert_eq!(panel.get_active_button(), Some(0));
+        assert!(
+            events.contains(&PanelEvent::ButtonActivated(0)),
+            "{events:?}"
+        );
+        assert!(events.contains(&PanelEvent::Expanded), "{events:?}");
+        assert!(
+            events.contains(&PanelEvent::AnimationFinished),
+            "{events:?}"
+        );
+
+        let rect = harness.settle(&mut panel).0.unwrap();
+        assert_close(thickness(side, rect), 300.0);
+    }
+}
+
+#[test]
+fn strip_caret_expands_horizontal_panels() {
+    for side in [PanelSide::Top, PanelSide::Bottom] {
+        let mut harness = Harness::new();
+        let mut panel = test_panel(side, Id::new(("strip_caret", side)));
+        panel.set_collapsed(true);
+        let rect = harness.settle(&mut panel).0.unwrap();
+
+        // The expand caret is the first item of the horizontal strip.
+        let events = harness.click(&mut panel, rect.left_top() + Vec2::new(6.0, 8.0));
+
+        assert!(!panel.is_collapsed(), "{side:?}");
+        assert!(events.contains(&PanelEvent::Expanded), "{events:?}");
+        let rect = harness.settle(&mut panel).0.unwrap();
+        assert_close(thickness(side, rect), 300.0);
+    }
+}
+
+#[test]
+fn programmatic_toggle_emits_events() {
+    let mut harness = Harness::new();
+    let mut panel = test_panel(PanelSide::Left, Id::new("toggle"));
+    harness.settle(&mut panel);
+
+    panel.toggle();
+    let events = harness.settle(&mut panel).1;
+    assert_eq!(
+        events,
+        vec![PanelEvent::Collapsed, PanelEvent::AnimationFinished]
+    );
+
+    panel.toggle();
+    let events = harness.settle(&mut panel).1;
+    assert_eq!(
+        events,
+        vec![PanelEvent::Expanded, PanelEvent::AnimationFinished]
+    );
+}
+
+#[test]
+fn zero_duration_skips_animation() {
+    let mut harness = Harness::new();
+    let mut panel =
+        test_panel(PanelSide::Left, Id::new("no_animation")).with_animation_duration(0.0);
+    harness.settle(&mut panel);
+
+    panel.set_collapsed(true);
+    let rect = harness.frame(&mut panel, Vec::new()).0.unwrap();
+    assert_eq!(rect.width(), COLLAPSED_SIZE);
+}
+
+/// Starts expanding a collapsed left panel with `transition` (one second,
+/// linear) and stops halfway. Returns the panel rect and the tab content shown
+/// halfway through, and the texts painted in the following frame.
+fn halfway_through(transition: TransitionMode) -> (Rect, ShownTab, Vec<String>) {
+    let mut harness = Harness::new();
+    let panel = test_panel(PanelSide::Left, Id::new(("transition", transition as u8)))
+        .with_transition(transition)
+        .with_animation_duration(1.0)
+        .with_easing(Easing::Linear);
+    let mut scene = PanelScene::new(panel);
+    scene.panel.set_collapsed(true);
+    harness.settle(&mut scene);
+
+    scene.panel.set_collapsed(false);
+    let mut rect = None;
+    for _ in 0..30 {
+        rect = harness.frame(&mut scene, Vec::new()).0;
+    }
+    let rect = rect.unwrap();
+    assert!(
+        rect.width() > 100.0 && rect.width() < 250.0,
+        "{transition:?} is halfway: {rect:?}"
+    );
+    let content = match scene.viewer.shown.as_slice() {
+        [shown] => shown.clone(),
+        shown => panic!("expected one shown tab, got {shown:?}"),
+    };
+    let texts = harness.painted_texts(&mut scene);
+    (rect, content, texts)
+}
+
+fn strip_is_painted(texts: &[String]) -> bool {
+    texts.iter().any(|text| text == phosphor::FOLDER)
+}
+
+#[test]
+fn clip_transition_lays_content_out_at_the_current_width() {
+    let (rect, content, texts) = halfway_through(TransitionMode::Clip);
+
+    assert!(content.rect.left() >= rect.left() - 1.0, "{content:?}");
+    assert!(content.rect.right() <= rect.right() + 1.0, "{content:?}");
+    assert_eq!(content.opacity, 1.0);
+    assert!(!strip_is_painted(&texts), "{texts:?}");
+}
+
+#[test]
+fn slide_transition_moves_full_size_content_with_the_inner_edge() {
+    let (rect, content, texts) = halfway_through(TransitionMode::Slide);
+
+    assert!(content.rect.width() > rect.width() + 50.0, "{content:?}");
+    assert!(content.rect.left() < rect.left() - 50.0, "{content:?}");
+    assert!(
+        (content.rect.right() - rect.right()).abs() < 10.0,
+        "{content:?}"
+    );
+    assert_eq!(content.opacity, 1.0);
+    assert!(!strip_is_painted(&texts), "{texts:?}");
+}
+
+#[test]
+fn reveal_transition_keeps_full_size_content_at_the_outer_edge() {
+    let (rect, content, texts) = halfway_through(TransitionMode::Reveal);
+
+    assert!(content.rect.width() > rect.width() + 50.0, "{content:?}");
+    assert!(
+        (content.rect.left() - rect.left()).abs() < 10.0,
+        "{content:?}"
+    );
+    assert!(content.rect.right() > rect.right() + 50.0, "{content:?}");
+    assert_eq!(content.opacity, 1.0);
+    assert!(!strip_is_painted(&texts), "{texts:?}");
+}
+
+#[test]
+fn cross_fade_transition_shows_strip_and_content_half_transparent() {
+    let (rect, content, texts) = halfway_through(TransitionMode::CrossFade);
+
+    assert!(content.rect.right() <= rect.right() + 1.0, "{content:?}");
+    assert!(
+        content.opacity > 0.1 && content.opacity < 0.9,
+        "{content:?}"
+    );
+    assert!(strip_is_painted(&texts), "{texts:?}");
+}
+
+/// `(old, new)` of every `Resized` event.
+fn resizes(events: &[PanelEvent]) -> Vec<(f32, f32)> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            PanelEvent::Resized { old, new } => Some((*old, *new)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn drag_resizes_every_side() {
+    for side in ALL_SIDES {
+        let mut harness = Harness::new();
+        let mut panel = test_panel(side, Id::new(("drag", side)));
+        let rect = harness.settle(&mut panel).0.unwrap();
+
+        let from = inner_edge(side, rect);
+        let events = harness.drag(&mut panel, from, from + grow(side, 100.0));
+
+        assert_close(panel.get_size(), 400.0);
+        // One event for the whole drag, sent when it ends.
+        let resized = resizes(&events);
+        assert_eq!(resized.len(), 1, "{side:?}: {events:?}");
+        assert_eq!(resized[0].0, 300.0, "{side:?}");
+        assert_close(resized[0].1, 400.0);
+        let rect = harness.settle(&mut panel).0.unwrap();
+        assert_close(thickness(side, rect), 400.0);
+    }
+}
+
+#[test]
+fn bottom_panel_resizes_up_and_down() {
+    let mut harness = Harness::new();
+    let mut panel = test_panel(PanelSide::Bottom, Id::new("bottom_up"));
+    let rect = harness.settle(&mut panel).0.unwrap();
+
+    let from = inner_edge(PanelSide::Bottom, rect);
+    harness.drag(&mut panel, from, from - Vec2::new(0.0, 120.0));
+    assert_eq!(panel.get_size(), 420.0);
+
+    let rect = harness.settle(&mut panel).0.unwrap();
+    let from = inner_edge(PanelSide::Bottom, rect);
+    harness.drag(&mut panel, from, from + Vec2::new(0.0, 60.0));
+    assert_eq!(panel.get_size(), 360.0);
+}
+
+#[test]
+fn small_drags_stay_below_save_threshold() {
+    let mut harness = Harness::new();
+    let mut panel = test_panel(PanelSide::Left, Id::new("threshold"));
+    let rect = harness.settle(&mut panel).0.unwrap();
+
+    // A 4px change is below the 5px threshold and must not be saved.
+    let from = inner_edge(PanelSide::Left, rect);
+    let events = harness.drag(&mut panel, from, from + Vec2::new(4.0, 0.0));
+    assert_eq!(panel.get_size(), 300.0);
+    assert!(
+        !events
+            .iter()
+            .any(|event| matches!(event, PanelEvent::Resized { .. })),
+        "{events:?}"
+    );
+
+    // Past the threshold the new size is saved.
+    let rect = harness.settle(&mut panel).0.unwrap();
+    let from = inner_edge(PanelSide::Left, rect);
+    harness.drag(&mut panel, from, from + Vec2::new(8.0, 0.0));
+    // The panel itself kept the 4px from the first drag.
+    assert_close(panel.get_size(), 312.0);
+}
+
+#[test]
+fn expanding_uses_saved_size_not_min_size() {
+    let mut harness = Harness::new();
+    let mut panel = test_panel(PanelSide::Left, Id::new("default_width")).with_min_size(200.0);
+    harness.settle(&mut panel);
+    let expanded_size = panel.get_size();
+    assert!(expanded_size > 200.0);
+
+    panel.set_collapsed(true);
+    harness.settle(&mut panel);
+    panel.set_collapsed(false);
+    let rect = harness.settle(&mut panel).0.unwrap();
+    assert_close(rect.width(), expanded_size);
+}
+
+#[test]
+fn sizes_reload_from_memory() {
+    let mut harness = Harness::new();
+    let id = Id::new("reload");
+
+    let mut state = CollapsibleDockState::new();
+    state.set_panel_size(PanelSide::Left, 420.0);
+    state.save_to_memory(&harness.ctx, id);
+
+    let loaded = CollapsibleDockState::load_from_memory(&harness.ctx, id);
+    assert_eq!(loaded.get_panel_size(PanelSide::Left), 420.0);
+
+    let mut panel = test_panel(PanelSide::Left, id);
+    let rect = harness.settle(&mut panel).0.unwrap();
+    assert_eq!(panel.get_size(), 420.0);
+    assert_close(rect.width(), 420.0);
+}
+
+#[test]
+fn collapsed_state_reloads_from_memory() {
+    let mut harness = Harness::new();
+    let id = Id::new("reload_collapsed");
+
+    let mut state = CollapsibleDockState::new();
+    state.set_panel_collapsed(PanelSide::Right, true);
+    state.save_to_memory(&harness.ctx, id);
+
+    let mut panel = test_panel(PanelSide::Right, id);
+    let (rect, events) = harness.settle(&mut panel);
+    assert!(panel.is_collapsed());
+    assert_eq!(rect.unwrap().width(), COLLAPSED_SIZE);
+    // Restoring a saved state is not a user-visible collapse.
+    assert!(!events.contains(&PanelEvent::Collapsed), "{events:?}");
+}
+
+#[test]
+fn invalid_saved_size_falls_back_to_default() {
+    let mut harness = Harness::new();
+    let id = Id::new("invalid_size");
+
+    let mut state = CollapsibleDockState::new();
+    state.panels.get_mut(&PanelSide::Left).unwrap().size = 50.0;
+    state.save_to_memory(&harness.ctx, id);
+
+    let mut panel = test_panel(PanelSide::Left, id);
+    harness.settle(&mut panel);
+    assert_eq!(panel.get_size(), 300.0);
+}
+
+#[test]
+fn panel_sizes_are_clamped_to_min_and_max() {
+    let mut state = CollapsibleDockState::new();
+    state.set_panel_size(PanelSide::Left, 50.0);
+    assert_eq!(state.get_panel_size(PanelSide::Left), 150.0);
+
+    state.panels.get_mut(&PanelSide::Left).unwrap().max_size = Some(400.0);
+    state.set_panel_size(PanelSide::Left, 180.0);
+    assert_eq!(state.get_panel_size(PanelSide::Left), 180.0);
+    state.set_panel_size(PanelSide::Left, 500.0);
+    assert_eq!(state.get_panel_size(PanelSide::Left), 400.0);
+}
+
+#[test]
+fn resized_size_survives_panel_rebuild() {
+    let mut harness = Harness::new();
+    let id = Id::new("rebuild");
+    let mut panel = test_panel(PanelSide::Right, id);
+    let rect = harness.settle(&mut panel).0.unwrap();
+
+    let from = inner_edge(PanelSide::Right, rect);
+    harness.drag(&mut panel, from, from + grow(PanelSide::Right, 50.0));
+    assert_eq!(panel.get_size(), 350.0);
+
+    // A freshly built panel with the same id picks the size up from memory.
+    let mut rebuilt = test_panel(PanelSide::Right, id);
+    harness.settle(&mut rebuilt);
+    assert_eq!(rebuilt.get_size(), 350.0);
+}
+
+#[test]
+fn programmatic_resize_animates_while_expanded() {
+    let mut harness = Harness::new();
+    let mut panel = test_panel(PanelSide::Left, Id::new("animated_resize"));
+    harness.settle(&mut panel);
+
+    panel.set_size(500.0);
+    for _ in 0..3 {
+        harness.frame(&mut panel, Vec::new());
+    }
+    let width = harness.frame(&mut panel, Vec::new()).0.unwrap().width();
+    assert!(
+        300.0 < width && width < 500.0,
+        "mid-animation width {width}"
+    );
+
+    let (rect, events) = harness.settle(&mut panel);
+    assert_close(rect.unwrap().width(), 500.0);
+    assert_eq!(
+        panel.get_size(),
+        500.0,
+        "egui's remembered width must not win"
+    );
+    assert!(
+        !events
+            .iter()
+            .any(|event| matches!(event, PanelEvent::Resized { .. })),
+        "{events:?}"
+    );
+}
+
+#[test]
+fn toggle_shortcut_collapses_and_expands() {
+    let mut harness = Harness::new();
+    let mut panel = test_panel(PanelSide::Left, Id::new("toggle_shortcut"))
+        .with_keymap(Keymap::default_for_side(PanelSide::Left));
+    harness.settle(&mut panel);
+
+    let events = harness
+        .frame(&mut panel, key_press(Modifiers::CTRL, Key::B))
+        .1;
+    assert!(panel.is_collapsed());
+    assert_eq!(events, [PanelEvent::Collapsed]);
+
+    harness.frame(&mut panel, key_press(Modifiers::CTRL, Key::B));
+    assert!(!panel.is_collapsed());
+}
+
+#[test]
+fn more_specific_shortcuts_win() {
+    let mut harness = Harness::new();
+    let mut panel = test_panel(PanelSide::Left, Id::new("specific_shortcut")).with_keymap(
+        Keymap::new()
+            .with_binding("Ctrl+B".parse().unwrap(), KeyAction::TogglePanel)
+            .with_binding("Ctrl+Shift+B".parse().unwrap(), KeyAction::FocusButton(1)),
+    );
+    harness.settle(&mut panel);
+
+    let events = harness
+        .frame(
+            &mut panel,
+            key_press(Modifiers::CTRL | Modifiers::SHIFT, Key::B),
+        )
+        .1;
+    assert!(!panel.is_collapsed());
+    assert_eq!(events, [PanelEvent::ButtonActivated(1)]);
+}
+
+#[test]
+fn focus_shortcut_activates_button_and_expands() {
+    let mut harness = Harness::new();
+    let mut panel = test_panel(PanelSide::Left, Id::new("focus_shortcut"))
+        .with_keymap(Keymap::default_for_side(PanelSide::Left));
+    panel.set_collapsed(true);
+    harness.settle(&mut panel);
+
+    let events = harness
+        .frame(&mut panel, key_press(Modifiers::ALT, Key::Num2))
+        .1;
+    assert!(!panel.is_collapsed());
+    assert_eq!(panel.get_active_button(), Some(1));
+    assert!(
+        events.contains(&PanelEvent::ButtonActivated(1)),
+        "{events:?}"
+    );
+
+    // Shortcuts past the last button are ignored.
+    harness.frame(&mut panel, key_press(Modifiers::ALT, Key::Num9));
+    assert_eq!(panel.get_active_button(), Some(1));
+}
+
+#[test]
+fn conflicting_shortcut_reports_instead_of_firing() {
+    let mut harness = Harness::new();
+    let ctrl_b: Shortcut = "Ctrl+B".parse().unwrap();
+    let mut panel = test_panel(PanelSide::Left, Id::new("conflict_shortcut")).with_keymap(
+        Keymap::new()
+            .with_binding(ctrl_b, KeyAction::TogglePanel)
+            .with_binding(ctrl_b, KeyAction::FocusButton(1)),
+    );
+    harness.settle(&mut panel);
+    assert_eq!(panel.keymap_conflicts().len(), 1);
+
+    let events = harness
+        .frame(&mut panel, key_press(Modifiers::CTRL, Key::B))
+        .1;
+    assert!(!panel.is_collapsed());
+    assert_eq!(panel.get_active_button(), Some(0));
+    assert_eq!(events, [PanelEvent::ShortcutConflict(ctrl_b)]);
+}
+
+#[test]
+fn tab_shortcuts_cycle_the_focused_leaf() {
+    let mut harness = Harness::new();
+    let mut panel = CollapsibleDockPanel::new(PanelSide::Left, Id::new("cycle_tabs"))
+        .with_dock_state(DockState::new(vec![
+            "Files".to_owned(),
+            "Search".to_owned(),
+            "Git".to_owned(),
+        ]))
+        .with_keymap(
+            Keymap::new()
+                .with_binding("Ctrl+Tab".parse().unwrap(), KeyAction::NextTab)
+                .with_binding("Ctrl+Shift+Tab".parse().unwrap(), KeyAction::PreviousTab),
+        );
+    harness.settle(&mut panel);
+
+    let focused = |panel: &mut TestPanel| {
+        panel
+            .dock_state_mut()
+            .find_active_focused()
+            .map(|(_, tab)| tab.clone())
+    };
+
+    harness.frame(&mut panel, key_press(Modifiers::CTRL, Key::Tab));
+    assert_eq!(focused(&mut panel).as_deref(), Some("Search"));
+
+    harness.frame(
+        &mut panel,
+        key_press(Modifiers::CTRL | Modifiers::SHIFT, Key::Tab),
+    );
+    harness.frame(
+        &mut panel,
+        key_press(Modifiers::CTRL | Modifiers::SHIFT, Key::Tab),
+    );
+    assert_eq!(focused(&mut panel).as_deref(), Some("Git"), "wraps around");
+}
+
+#[test]
+fn tab_shortcuts_start_from_the_active_tab_without_prior_focus() {
+    let mut harness = Harness::new();
+    let mut dock_state = DockState::new(vec![
+        "Files".to_owned(),
+        "Search".to_owned(),
+        "Git".to_owned(),
+    ]);
+    dock_state.set_active_tab((SurfaceIndex::main(), NodeIndex::root(), TabIndex(1)));
+    let mut panel = CollapsibleDockPanel::new(PanelSide::Left, Id::new("cycle_unfocused"))
+        .with_dock_state(dock_state)
+        .with_keymap(Keymap::new().with_binding("Ctrl+Tab".parse().unwrap(), KeyAction::NextTab));
+    harness.settle(&mut panel);
+    assert!(panel.dock_state().focused_leaf().is_none());
+
+    harness.frame(&mut panel, key_press(Modifiers::CTRL, Key::Tab));
+    assert_eq!(focused_tab(&mut panel).as_deref(), Some("Git"));
+}
+
+/// Left panel whose three strip buttons are bound to the tabs of the same name.
+fn bound_panel(id: &str) -> TestPanel {
+    let tabs = ["Explorer", "Search", "Git"];
+    tabs.iter().fold(
+        CollapsibleDockPanel::new(PanelSide::Left, Id::new(id)).with_dock_state(DockState::new(
+            tabs.iter().map(|tab| tab.to_string()).collect(),
+        )),
+        |panel, tab| panel.add_button_for_tab(CollapsibleButton::new(*tab), tab.to_string()),
+    )
+}
+
+fn focused_tab(panel: &mut TestPanel) -> Option<String> {
+    panel
+        .dock_state_mut()
+        .find_active_focused()
+        .map(|(_, tab)| tab.clone())
+}
+
+#[test]
+fn clicking_a_bound_button_focuses_its_tab() {
+    let mut harness = Harness::new();
+    let mut panel = bound_panel("bound_click");
+    panel.set_collapsed(true);
+    let strip = harness.settle(&mut panel).0.unwrap();
+
+    let events = harness.click(&mut panel, strip_button(strip, 2));
+    assert!(
+        events.contains(&PanelEvent::ButtonActivated(2)),
+        "{events:?}"
+    );
+    assert!(!panel.is_collapsed());
+    assert_eq!(focused_tab(&mut panel).as_deref(), Some("Git"));
+    assert_eq!(
+        panel.get_active_button(),
+        Some(2),
+        "the sync back from the dock keeps the clicked button"
+    );
+}
+
+#[test]
+fn focusing_a_tab_activates_its_bound_button() {
+    let mut harness = Harness::new();
+    let mut panel = bound_panel("bound_focus");
+    harness.settle(&mut panel);
+    assert_eq!(panel.get_active_button(), Some(0));
+
+    let dock_state = panel.dock_state_mut();
+    let path = dock_state.find_tab(&"Search".to_owned()).unwrap();
+    dock_state.set_focused_node_and_surface((path.0, path.1));
+    dock_state.set_active_tab(path);
+    harness.frame(&mut panel, Vec::new());
+
+    assert_eq!(panel.get_active_button(), Some(1));
+    assert_eq!(focused_tab(&mut panel).as_deref(), Some("Search"));
+}
+
+fn overlay_panel(id: &str, expand_mode: ExpandMode) -> TestPanel {
+    let mut panel = test_panel(PanelSide::Left, Id::new(id)).with_expand_mode(expand_mode);
+    panel.set_collapsed(true);
+    panel
+}
+
+#[test]
+fn overlay_opens_without_changing_layout() {
+    let mut harness = Harness::new();
+    let mut panel = overlay_panel("overlay_click", ExpandMode::Overlay);
+    let strip = harness.settle(&mut panel).0.unwrap();
+
+    let events = harness.click(&mut panel, first_strip_button(strip));
+    let rect = harness.frame(&mut panel, Vec::new()).0.unwrap();
+
+    assert!(panel.is_collapsed());
+    assert!(panel.is_overlay_open());
+    assert_eq!(rect.width(), COLLAPSED_SIZE, "layout must not reflow");
+    assert!(
+        events.contains(&PanelEvent::ButtonActivated(0)),
+        "{events:?}"
+    );
+    assert!(events.contains(&PanelEvent::OverlayOpened), "{events:?}");
+    assert!(!events.contains(&PanelEvent::Expanded), "{events:?}");
+
+    let overlay = harness
+        .ctx
+        .memory(|mem| mem.area_rect(Id::new("overlay_click").with("overlay")))
+        .expect("overlay area is shown");
+    assert_close(overlay.left(), strip.right());
+    assert_close(overlay.width(), panel.get_size());
+}
+
+#[test]
+fn overlay_dismisses_on_escape_and_outside_click() {
+    let mut harness = Harness::new();
+    let mut panel = overlay_panel("overlay_dismiss", ExpandMode::Overlay);
+    let strip = harness.settle(&mut panel).0.unwrap();
+
+    harness.click(&mut panel, first_strip_button(strip));
+    assert!(panel.is_overlay_open());
+    let events = harness
+        .frame(&mut panel, key_press(Modifiers::NONE, Key::Escape))
+        .1;
+    assert!(!panel.is_overlay_open());
+    assert_eq!(events, [PanelEvent::OverlayClosed]);
+
+    harness.click(&mut panel, first_strip_button(strip));
+    assert!(panel.is_overlay_open());
+    harness.click(&mut panel, Pos2::new(900.0, 400.0));
+    assert!(!panel.is_overlay_open());
+}
+
+#[test]
+fn pressing_a_popup_opened_from_the_overlay_keeps_it_open() {
+    let mut harness = Harness::new();
+    let mut panel = overlay_panel("overlay_popup", ExpandMode::Overlay);
+    panel.set_size(160.0);
+    let mut scene = PanelScene::new(panel);
+    let wide_option = "An option much wider than the narrow overlay";
+    scene.viewer.combo_box = vec![wide_option];
+    let strip = harness.settle(&mut scene).0.unwrap();
+    harness.click(&mut scene, first_strip_button(strip));
+    assert!(scene.panel.is_overlay_open());
+
+    let text_at = |harness: &mut Harness, scene: &mut PanelScene, text: &str| {
+        harness
+            .painted_text_rects(scene)
+            .into_iter()
+            .find(|(painted, _)| painted == text)
+            .unwrap_or_else(|| panic!("{text} is not painted"))
+            .1
+    };
+    let combo_box = text_at(&mut harness, &mut scene, "Pick").center();
+    harness.click(&mut scene, combo_box);
+    assert!(scene.panel.is_overlay_open());
+
+    // Press the part of the option that sticks out of the overlay
+    let overlay = harness
+        .ctx
+        .memory(|mem| mem.area_rect(Id::new("overlay_popup").with("overlay")))
+        .unwrap();
+    let option = text_at(&mut harness, &mut scene, wide_option);
+    assert!(
+        option.right() > overlay.right() + 10.0,
+        "{option:?} {overlay:?}"
+    );
+    harness.click(
+        &mut scene,
+        Pos2::new(overlay.right() + 5.0, option.center().y),
+    );
+    assert!(scene.panel.is_overlay_open());
+
+    // With the popup closed, the same press is outside again
+    harness.click(
+        &mut scene,
+        Pos2::new(overlay.right() + 5.0, option.center().y),
+    );
+    assert!(!scene.panel.is_overlay_open());
+}
+
+#[test]
+fn overlay_on_hover_opens_from_pointer_over_strip() {
+    let mut harness = Harness::new();
+    let mut panel = overlay_panel("overlay_hover", ExpandMode::OverlayOnHover);
+    let strip = harness.settle(&mut panel).0.unwrap();
+
+    harness.frame(
+        &mut panel,
+        vec![Event::PointerMoved(first_strip_button(strip))],
+    );
+    harness.frame(&mut panel, Vec::new());
+
+    assert!(panel.is_overlay_open());
+    assert!(panel.is_collapsed());
+}
+
+#[test]
+fn docking_the_panel_closes_the_overlay() {
+    let mut harness = Harness::new();
+    let mut panel = overlay_panel("overlay_docked", ExpandMode::Overlay);
+    let strip = harness.settle(&mut panel).0.unwrap();
+    harness.click(&mut panel, first_strip_button(strip));
+
+    panel.set_collapsed(false);
+    let events = harness.frame(&mut panel, Vec::new()).1;
+
+    assert!(!panel.is_overlay_open());
+    assert!(events.contains(&PanelEvent::OverlayClosed), "{events:?}");
+}
+
+fn unpinned_panel(id: &str) -> TestPanel {
+    test_panel(PanelSide::Left, Id::new(id))
+        .with_pinned(false)
+        .with_auto_collapse_delay(0.3)
+}
+
+#[test]
+fn unpinned_panel_collapses_after_pointer_leaves() {
+    let mut harness = Harness::new();
+    let mut panel = unpinned_panel("unpinned_leave");
+    harness.frame(
+        &mut panel,
+        vec![Event::PointerMoved(Pos2::new(100.0, 400.0))],
+    );
+    harness.settle(&mut panel);
+    assert!(!panel.is_collapsed(), "pointer inside keeps it open");
+
+    harness.frame(
+        &mut panel,
+        vec![Event::PointerMoved(Pos2::new(900.0, 400.0))],
+    );
+    for _ in 0..6 {
+        harness.frame(&mut panel, Vec::new());
+    }
+    assert!(!panel.is_collapsed(), "still within the delay");
+
+    harness.settle(&mut panel);
+    assert!(panel.is_collapsed());
+}
+
+#[test]
+fn unpinned_panel_collapses_when_clicked_outside() {
+    let mut harness = Harness::new();
+    let mut panel = unpinned_panel("unpinned_click").with_auto_collapse_delay(10.0);
+    harness.frame(
+        &mut panel,
+        vec![Event::PointerMoved(Pos2::new(100.0, 400.0))],
+    );
+    harness.settle(&mut panel);
+
+    let outside = Pos2::new(900.0, 400.0);
+    harness.frame(&mut panel, vec![Event::PointerMoved(outside)]);
+    let events = harness
+        .frame(&mut panel, vec![pointer_button(outside, true)])
+        .1;
+    assert!(panel.is_collapsed());
+    assert!(events.contains(&PanelEvent::Collapsed), "{events:?}");
+}
+
+#[test]
+fn pressing_the_separator_outside_an_unpinned_panel_keeps_it_open() {
+    let mut harness = Harness::new();
+    // At its max size the panel edge cannot follow the pointer out.
+    let mut panel = unpinned_panel("unpinned_resize")
+        .with_auto_collapse_delay(10.0)
+        .with_max_size(300.0);
+    harness.frame(
+        &mut panel,
+        vec![Event::PointerMoved(Pos2::new(100.0, 400.0))],
+    );
+    let rect = harness.settle(&mut panel).0.unwrap();
+
+    // The outer half of the separator's grab area lies past the panel rect.
+    let edge = Pos2::new(rect.right() + 2.0, rect.center().y);
+    harness.drag(&mut panel, edge, edge + Vec2::new(40.0, 0.0));
+    assert!(!panel.is_collapsed());
+    assert_close(panel.get_size(), 300.0);
+}
+
+#[test]
+fn pin_button_toggles_and_persists() {
+    let mut harness = Harness::new();
+    let id = Id::new("pin_button");
+    let mut panel = test_panel(PanelSide::Left, id);
+    let rect = harness.settle(&mut panel).0.unwrap();
+    assert!(panel.is_pinned());
+
+    let pin_button = Pos2::new(rect.right() - 13.0, rect.top() + 13.0);
+    harness.click(&mut panel, pin_button);
+    assert!(!panel.is_pinned());
+    assert!(!panel.is_collapsed(), "pointer is still over the panel");
+
+    let mut rebuilt = test_panel(PanelSide::Left, id);
+    harness.frame(&mut rebuilt, vec![Event::PointerMoved(pin_button)]);
+    assert!(!rebuilt.is_pinned());
+}
+
+fn three_button_panel(id: Id) -> TestPanel {
+    let mut panel = test_panel(PanelSide::Left, id).add_button(CollapsibleButton::new("Git"));
+    panel.set_collapsed(true);
+    panel
+}
+
+#[test]
+fn dragging_strip_buttons_reorders_and_persists() {
+    let mut harness = Harness::new();
+    let id = Id::new("reorder");
+    let mut panel = three_button_panel(id);
+    let rect = harness.settle(&mut panel).0.unwrap();
+
+    let first = first_strip_button(rect);
+    let events = harness.drag(&mut panel, first, first + Vec2::new(0.0, 44.0));
+    assert!(events.contains(&PanelEvent::ButtonsReordered), "{events:?}");
+    assert!(panel.is_collapsed(), "dragging does not expand the panel");
+    assert_eq!(panel.button_order(), ["Search", "Git", "Files"]);
+
+    // A later app version adds a button: the saved order still applies.
+    let mut rebuilt = three_button_panel(id).add_button(CollapsibleButton::new("Debug"));
+    harness.settle(&mut rebuilt);
+    assert_eq!(rebuilt.button_order(), ["Search", "Git", "Files", "Debug"]);
+
+    // Clicking the first visible button activates it by its original index.
+    let events = harness.click(&mut rebuilt, first);
+    assert!(
+        events.contains(&PanelEvent::ButtonActivated(1)),
+        "{events:?}"
+    );
+}
+
+#[test]
+fn button_order_is_keyed_by_stable_id() {
+    let mut panel = CollapsibleDockPanel::<TestViewer>::new(PanelSide::Left, Id::new("ids"))
+        .add_button(CollapsibleButton::new("Dateien").with_id("files"))
+        .add_button(CollapsibleButton::new("Suche").with_id("search"))
+        .with_keymap(Keymap::default_for_side(PanelSide::Left));
+
+    panel.set_button_order(["search", "removed-in-this-version"]);
+    assert_eq!(panel.button_order(), ["search", "files"]);
+
+    // Alt+1 activates the first button in display order.
+    let mut harness = Harness::new();
+    let events = harness
+        .frame(&mut panel, key_press(Modifiers::ALT, Key::Num1))
+        .1;
+    assert!(
+        events.contains(&PanelEvent::ButtonActivated(1)),
+        "{events:?}"
+    );
+
+    panel.reset_button_order();
+    assert_eq!(panel.button_order(), ["files", "search"]);
+}
+
+#[test]
+fn badges_update_every_frame_without_rebuilding() {
+    for side in [PanelSide::Left, PanelSide::Bottom] {
+        let mut harness = Harness::new();
+        let mut panel = test_panel(side, Id::new(("badges", side)))
+            .add_button(CollapsibleButton::new("Problems").with_badge(Badge::count(7)));
+        panel.set_collapsed(true);
+        harness.settle(&mut panel);
+        assert!(harness.painted_texts(&mut panel).contains(&"7".to_owned()));
+
+        let errors = Badge::count(120).with_severity(BadgeSeverity::Error);
+        assert!(panel.set_badge("Problems", Some(errors)));
+        assert!(harness
+            .painted_texts(&mut panel)
+            .contains(&"99+".to_owned()));
+
+        assert!(panel.set_badge("Problems", Some(Badge::count(0))));
+        let texts = harness.painted_texts(&mut panel);
+        assert!(!texts.iter().any(|text| text == "0"), "{texts:?}");
+
+        assert!(!panel.set_badge("Missing", Some(Badge::dot())));
+    }
+}
+
+#[test]
+fn badge_labels() {
+    assert_eq!(Badge::count(3).label().as_deref(), Some("3"));
+    assert_eq!(Badge::count(100).label().as_deref(), Some("99+"));
+    assert_eq!(Badge::dot().label(), None);
+    assert!(Badge::dot().is_visible());
+    assert!(!Badge::count(0).is_visible());
+}
+
+#[test]
+fn strip_menu_hides_buttons_persistently() {
+    let mut harness = Harness::new();
+    let id = Id::new("strip_menu_hide");
+    let mut panel = three_button_panel(id);
+    let rect = harness.settle(&mut panel).0.unwrap();
+
+    let first = first_strip_button(rect);
+    harness.right_click(&mut panel, first);
+    // The first entry hides the right-clicked button.
+    harness.click(&mut panel, first + Vec2::new(20.0, 12.0));
+    assert!(panel.is_button_hidden("Files"));
+    assert!(panel.is_collapsed());
+
+    // "Search" now occupies the first slot.
+    let events = harness.click(&mut panel, first);
+    assert!(
+        events.contains(&PanelEvent::ButtonActivated(1)),
+        "{events:?}"
+    );
+
+    let mut rebuilt = three_button_panel(id);
+    harness.settle(&mut rebuilt);
+    assert!(rebuilt.is_button_hidden("Files"));
+
+    rebuilt.unhide_all_buttons();
+    assert!(!rebuilt.is_button_hidden("Files"));
+    assert!(!rebuilt.set_button_hidden("Missing", true));
+}
+
+#[test]
+fn strip_menu_calls_the_app_callback() {
+    let seen = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let mut harness = Harness::new();
+    let mut panel = three_button_panel(Id::new("strip_menu_callback")).with_strip_menu({
+        let seen = seen.clone();
+        move |ui, button| {
+            seen.borrow_mut().push(button.map(str::to_owned));
+            ui.label("custom");
+        }
+    });
+    let rect = harness.settle(&mut panel).0.unwrap();
+
+    harness.right_click(&mut panel, first_strip_button(rect));
+    assert_eq!(seen.borrow().last(), Some(&Some("Files".to_owned())));
+
+    // Close the menu, then open it on the empty part of the strip.
+    harness.frame(&mut panel, key_press(Modifiers::NONE, Key::Escape));
+    harness.right_click(&mut panel, Pos2::new(rect.center().x, rect.bottom() - 20.0));
+    assert_eq!(seen.borrow().last(), Some(&None));
+}
+
+#[test]
+fn reset_size_returns_to_the_default_size() {
+    let mut harness = Harness::new();
+    let mut panel = test_panel(PanelSide::Left, Id::new("reset_size")).with_default_size(260.0);
+    harness.settle(&mut panel);
+    panel.set_size(420.0);
+    harness.settle(&mut panel);
+
+    panel.reset_size();
+    let rect = harness.settle(&mut panel).0.unwrap();
+    assert_close(rect.width(), 260.0);
+    assert_eq!(panel.default_size(), 260.0);
+}
+
+#[test]
+fn set_side_keeps_size_and_collapse_state() {
+    let mut harness = Harness::new();
+    let mut panel = test_panel(PanelSide::Left, Id::new("set_side")).with_default_size(280.0);
+    harness.settle(&mut panel);
+
+    panel.set_side(PanelSide::Right);
+    let rect = harness.settle(&mut panel).0.unwrap();
+    assert_eq!(panel.side(), PanelSide::Right);
+    assert_eq!(rect.right(), SCREEN_SIZE.x);
+    assert_close(rect.width(), 280.0);
+}
+
+#[test]
+fn set_side_between_frames_is_reported_by_the_next_show() {
+    let mut harness = Harness::new();
+    let mut panel = test_panel(PanelSide::Left, Id::new("set_side_event"));
+    harness.settle(&mut panel);
+
+    panel.set_side(PanelSide::Top);
+    let (_, events) = harness.frame(&mut panel, Vec::new());
+    assert_eq!(
+        events,
+        [PanelEvent::Moved {
+            from: PanelSide::Left,
+            to: PanelSide::Top,
+        }]
+    );
+    assert!(harness.frame(&mut panel, Vec::new()).1.is_empty());
+}
+
+#[test]
+fn moving_across_orientations_translates_the_size_and_persists() {
+    let mut harness = Harness::new();
+    let id = Id::new("move_side");
+    let mut panel = test_panel(PanelSide::Left, id).with_default_size(320.0);
+    harness.settle(&mut panel);
+
+    panel.set_side(PanelSide::Bottom);
+    let rect = harness.settle(&mut panel).0.unwrap();
+    assert_eq!(rect.bottom(), SCREEN_SIZE.y);
+    assert_close(rect.height(), 320.0 * SCREEN_SIZE.y / SCREEN_SIZE.x);
+
+    let mut rebuilt = test_panel(PanelSide::Left, id);
+    harness.settle(&mut rebuilt);
+    assert_eq!(rebuilt.home_side(), PanelSide::Left);
+    assert_eq!(rebuilt.side(), PanelSide::Bottom);
+}
+
+#[test]
+fn breakpoints_collapse_and_restore_the_previous_state() {
+    let mut harness = Harness::new();
+    let mut expanded = test_panel(PanelSide::Left, Id::new("responsive_expanded"))
+        .with_breakpoint(Breakpoint::WindowWidthBelow(1000.0));
+    let mut collapsed = test_panel(PanelSide::Left, Id::new("responsive_collapsed"))
+        .with_breakpoint(Breakpoint::WindowWidthBelow(1000.0));
+    collapsed.set_collapsed(true);
+    harness.settle(&mut expanded);
+    harness.settle(&mut collapsed);
+
+    harness.screen_size = Vec2::new(900.0, 800.0);
+    harness.settle(&mut expanded);
+    harness.settle(&mut collapsed);
+    assert!(expanded.is_collapsed());
+    assert!(expanded.is_auto_collapsed());
+    assert!(collapsed.is_collapsed());
+    assert!(!collapsed.is_auto_collapsed());
+
+    harness.screen_size = SCREEN_SIZE;
+    harness.settle(&mut expanded);
+    harness.settle(&mut collapsed);
+    assert!(!expanded.is_collapsed());
+    assert!(collapsed.is_collapsed());
+}
+
+#[test]
+fn manual_expand_inside_a_breakpoint_sticks() {
+    let mut harness = Harness::new();
+    harness.screen_size = Vec2::new(900.0, 800.0);
+    let mut panel = test_panel(PanelSide::Left, Id::new("responsive_manual"))
+        .with_breakpoint(Breakpoint::WindowWidthBelow(1000.0));
+    harness.settle(&mut panel);
+    assert!(panel.is_collapsed());
+
+    panel.set_collapsed(false);
+    harness.settle(&mut panel);
+    assert!(!panel.is_collapsed());
+    assert!(!panel.is_auto_collapsed());
+}
+
+#[test]
+fn dragging_the_edge_below_the_snap_threshold_collapses() {
+    let mut harness = Harness::new();
+    let mut panel = test_panel(PanelSide::Left, Id::new("snap_collapse")).with_default_size(300.0);
+    let rect = harness.settle(&mut panel).0.unwrap();
+    let min_size = panel.panel_state().min_size;
+
+    let edge = Pos2::new(rect.right(), rect.center().y);
+    let events = harness.drag(&mut panel, edge, Pos2::new(min_size * 0.25, edge.y));
+
+    assert!(panel.is_collapsed());
+    assert_close(panel.get_size(), 300.0);
+    assert!(events.contains(&PanelEvent::Collapsed), "{events:?}");
+    assert!(
+        resizes(&events).is_empty(),
+        "the drag shrank the panel before it snapped: {events:?}"
+    );
+}
+
+#[test]
+fn dragging_out_of_the_strip_expands_to_the_dragged_size() {
+    for side in ALL_SIDES {
+        let mut harness = Harness::new();
+        let mut panel = test_panel(side, Id::new(("snap_expand", side)));
+        panel.set_collapsed(true);
+        let strip = harness.settle(&mut panel).0.unwrap();
+
+        // Drag from the inner edge of the strip to 400px from the window edge.
+        let (from, to) = match side {
+            PanelSide::Left => (
+                Pos2::new(strip.right() - 1.0, strip.center().y),
+                Pos2::new(400.0, strip.center().y),
+            ),
+            PanelSide::Right => (
+                Pos2::new(strip.left() + 1.0, strip.center().y),
+                Pos2::new(SCREEN_SIZE.x - 400.0, strip.center().y),
+            ),
+            PanelSide::Top => (
+                Pos2::new(strip.center().x, strip.bottom() - 1.0),
+                Pos2::new(strip.center().x, 400.0),
+            ),
+            PanelSide::Bottom => (
+                Pos2::new(strip.center().x, strip.top() + 1.0),
+                Pos2::new(strip.center().x, SCREEN_SIZE.y - 400.0),
+            ),
+        };
+        let events = harness.drag(&mut panel, from, to);
+
+        assert!(!panel.is_collapsed(), "{side:?}");
+        assert_close(panel.get_size(), 400.0);
+        assert!(events.contains(&PanelEvent::Expanded), "{side:?}");
+    }
+}
+
+#[test]
+fn double_clicking_the_separator_cycles_sizes() {
+    let mut harness = Harness::new();
+    let mut panel = test_panel(PanelSide::Left, Id::new("size_cycle"))
+        .with_default_size(250.0)
+        .with_maximize(200.0);
+    panel.set_size(400.0);
+    let mut rect = harness.settle(&mut panel).0.unwrap();
+
+    let mut widths = Vec::new();
+    for _ in 0..3 {
+        harness.double_click(&mut panel, Pos2::new(rect.right(), rect.center().y));
+        rect = harness.settle(&mut panel).0.unwrap();
+        widths.push(rect.width());
+    }
+    assert_close(widths[0], 250.0);
+    assert_close(widths[1], SCREEN_SIZE.x - 200.0);
+    assert!(panel.is_collapsed());
+    assert_close(panel.get_size(), 400.0);
+
+    harness.double_click(&mut panel, Pos2::new(rect.right() - 1.0, rect.center().y));
+    let rect = harness.settle(&mut panel).0.unwrap();
+    assert!(!panel.is_collapsed());
+    assert_close(rect.width(), 400.0);
+}
+
+#[test]
+fn calling_cycle_size_between_frames_reports_resized() {
+    let mut harness = Harness::new();
+    let mut panel = test_panel(PanelSide::Left, Id::new("size_cycle_api")).with_default_size(250.0);
+    panel.set_size(400.0);
+    harness.settle(&mut panel);
+
+    panel.cycle_size();
+    let (_, events) = harness.frame(&mut panel, Vec::new());
+    assert_eq!(resizes(&events), [(400.0, 250.0)]);
+}
+
+#[test]
+fn strip_menu_uses_the_configured_strings() {
+    let mut harness = Harness::new();
+    let mut panel = three_button_panel(Id::new("strip_menu_strings"))
+        .with_strings(Strings::english().with(StringKey::ResetSize, "Größe zurücksetzen"));
+    let rect = harness.settle(&mut panel).0.unwrap();
+
+    harness.right_click(&mut panel, first_strip_button(rect));
+    let texts = harness.painted_texts(&mut panel);
+    for label in [
+        "Hide “Files”",
+        "Move to",
+        "Größe zurücksetzen",
+        "Expand panel",
+    ] {
+        assert!(texts.iter().any(|text| text == label), "{label}: {texts:?}");
+    }
+    assert!(!texts.iter().any(|text| text == "重置尺寸"), "{texts:?}");
+}
+
+#[test]
+fn strings_fall_back_to_the_base_locale() {
+    let strings = Strings::chinese().with(StringKey::CloseTab, "Tab schließen");
+    assert_eq!(strings.get(StringKey::CloseTab), "Tab schließen");
+    assert_eq!(strings.get(StringKey::ExpandPanel), "展开面板");
+    assert_eq!(Strings::default().locale(), Locale::Chinese);
+    assert_eq!(
+        Strings::english().format(StringKey::HideButton, "Git"),
+        "Hide “Git”"
+    );
+}
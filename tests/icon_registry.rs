@@ -0,0 +1,97 @@
+//! Icon lookup through `IconRegistry` and the strip buttons that use it.
+
+mod common;
+
+use common::{Harness, TestPanel};
+use egui::{Color32, Context, Id, LayerId, Painter, Rect};
+use egui_collapsible_dock::{
+    CollapsibleButton, CollapsibleDockPanel, Icon, IconRegistry, PanelSide,
+};
+use egui_phosphor::regular as phosphor;
+
+/// Runs `paint` with a painter inside a throwaway frame.
+fn with_painter<R>(paint: impl FnOnce(&Painter) -> R) -> R {
+    let ctx = Context::default();
+    let mut paint = Some(paint);
+    let mut result = None;
+    let _ = ctx.run(Default::default(), |ctx| {
+        let painter = Painter::new(ctx.clone(), LayerId::background(), Rect::EVERYTHING);
+        result = paint.take().map(|paint| paint(&painter));
+    });
+    result.expect("frame ran once")
+}
+
+#[test]
+fn svg_keys_and_button_texts_are_separate_namespaces() {
+    let registry = IconRegistry::with_defaults();
+
+    assert!(matches!(registry.get("Files"), Some(Icon::Glyph(glyph)) if glyph == phosphor::FOLDER));
+    assert!(
+        matches!(registry.get("Settings"), Some(Icon::Glyph(glyph)) if glyph == phosphor::GEAR)
+    );
+    assert!(matches!(registry.get("svg:Files"), Some(Icon::Painter(_))));
+    assert!(matches!(
+        registry.get("svg:Settings"),
+        Some(Icon::Painter(_))
+    ));
+    assert!(
+        !registry.contains("SceneTree"),
+        "svg keys keep their prefix"
+    );
+
+    assert_eq!(CollapsibleButton::new("Files").icon_key(), "Files");
+    let svg_button = CollapsibleButton::new("Files").with_icon("svg:Files");
+    assert_eq!(svg_button.icon_key(), "svg:Files");
+}
+
+#[test]
+fn registering_overrides_and_unregistering_falls_back() {
+    let mut registry = IconRegistry::with_defaults();
+    registry.register_glyph("Files", "📁");
+    assert!(matches!(registry.get("Files"), Some(Icon::Glyph(glyph)) if glyph == "📁"));
+    assert!(
+        matches!(registry.get("svg:Files"), Some(Icon::Painter(_))),
+        "overriding a text key leaves the svg key alone"
+    );
+
+    assert!(registry.unregister("Files").is_some());
+    assert!(registry.get("Files").is_none());
+    assert!(registry.unregister("Files").is_none());
+}
+
+#[test]
+fn painting_reports_whether_the_key_was_registered() {
+    let registry = IconRegistry::with_defaults();
+    let rect = Rect::from_min_size(egui::Pos2::ZERO, egui::Vec2::splat(14.0));
+
+    with_painter(|painter| {
+        assert!(registry.paint(painter, "Search", rect, Color32::WHITE));
+        assert!(registry.paint(painter, "svg:Console", rect, Color32::WHITE));
+        assert!(!registry.paint(painter, "Missing", rect, Color32::WHITE));
+        assert!(!IconRegistry::new().paint(painter, "Search", rect, Color32::WHITE));
+    });
+}
+
+#[test]
+fn strip_buttons_paint_registered_glyphs_and_fall_back_to_icon_text() {
+    for side in [PanelSide::Left, PanelSide::Bottom] {
+        let mut harness = Harness::new();
+        let mut panel: TestPanel = CollapsibleDockPanel::new(side, Id::new(("icons", side)))
+            .add_button(CollapsibleButton::new("Files"))
+            .add_button(CollapsibleButton::new("Custom").with_icon("🧪"))
+            .add_button(CollapsibleButton::new("Unknown"));
+        panel.set_collapsed(true);
+        harness.settle(&mut panel);
+
+        let texts = harness.painted_texts(&mut panel);
+        assert!(
+            texts.iter().any(|text| text == phosphor::FOLDER),
+            "{side:?}: {texts:?}"
+        );
+        assert!(texts.iter().any(|text| text == "🧪"), "{side:?}: {texts:?}");
+        assert!(
+            !texts.iter().any(|text| text == "Unknown"),
+            "unregistered buttons use the fallback dot: {texts:?}"
+        );
+    }
+}
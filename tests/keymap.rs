@@ -0,0 +1,99 @@
+//! Shortcut parsing, keymap serialization and conflict diagnostics.
+
+use egui::{Key, Modifiers};
+use egui_collapsible_dock::{find_conflicts, KeyAction, KeyBinding, Keymap, PanelSide, Shortcut};
+
+#[test]
+fn shortcuts_round_trip_through_strings() {
+    let shortcut: Shortcut = "Ctrl+Shift+B".parse().unwrap();
+    assert_eq!(
+        shortcut,
+        Shortcut::new(Modifiers::CTRL | Modifiers::SHIFT, Key::B)
+    );
+    assert_eq!(shortcut.to_string(), "Ctrl+Shift+B");
+
+    assert_eq!("alt + 1".parse::<Shortcut>().unwrap().to_string(), "Alt+1");
+    assert!("Ctrl+Nope".parse::<Shortcut>().is_err());
+    assert!("Hyper+B".parse::<Shortcut>().is_err());
+}
+
+#[test]
+fn keymaps_serialize_as_readable_tables() {
+    let keymap = Keymap::default_for_side(PanelSide::Left);
+    let json = serde_json::to_string(&keymap).unwrap();
+    assert!(json.contains(r#""shortcut":"Ctrl+B""#), "{json}");
+
+    let loaded: Keymap = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, keymap);
+
+    let invalid = r#"{"bindings":[{"shortcut":"Ctrl+","action":"TogglePanel"}]}"#;
+    assert!(serde_json::from_str::<Keymap>(invalid).is_err());
+}
+
+#[test]
+fn default_keymaps_do_not_conflict() {
+    let keymaps: Vec<_> = [
+        PanelSide::Left,
+        PanelSide::Right,
+        PanelSide::Top,
+        PanelSide::Bottom,
+    ]
+    .into_iter()
+    .map(|side| (side, Keymap::default_for_side(side)))
+    .collect();
+
+    let conflicts = find_conflicts(keymaps.iter().map(|(side, keymap)| (*side, keymap)));
+    assert!(conflicts.is_empty(), "{conflicts:?}");
+}
+
+#[test]
+fn conflicts_are_reported_within_and_across_keymaps() {
+    let ctrl_b = Shortcut::new(Modifiers::CTRL, Key::B);
+    let left = Keymap::new()
+        .with_binding(ctrl_b, KeyAction::TogglePanel)
+        .with_binding(ctrl_b, KeyAction::NextTab);
+    let right = Keymap::new().with_binding(ctrl_b, KeyAction::TogglePanel);
+
+    let conflicts = find_conflicts([(PanelSide::Left, &left), (PanelSide::Right, &right)]);
+    assert_eq!(conflicts.len(), 1);
+    let binding = |action| KeyBinding {
+        shortcut: ctrl_b,
+        action,
+    };
+    assert_eq!(
+        conflicts[0].bindings,
+        [
+            (PanelSide::Left, binding(KeyAction::TogglePanel)),
+            (PanelSide::Left, binding(KeyAction::NextTab)),
+            (PanelSide::Right, binding(KeyAction::TogglePanel)),
+        ]
+    );
+    assert!(conflicts[0].to_string().starts_with("Ctrl+B triggers"));
+}
+
+#[test]
+fn shortcuts_that_match_logically_conflict() {
+    let ctrl_b: Shortcut = "Ctrl+B".parse().unwrap();
+    let ctrl_alt_b: Shortcut = "Ctrl+Alt+B".parse().unwrap();
+    let cmd_b: Shortcut = "Cmd+B".parse().unwrap();
+
+    // egui ignores extra Alt/Shift, and Ctrl counts as Cmd outside macOS.
+    assert!(ctrl_b.matches(ctrl_alt_b));
+    assert!(!ctrl_alt_b.matches(ctrl_b));
+    assert!(cmd_b.matches(ctrl_b) && ctrl_b.matches(cmd_b));
+    assert!(!ctrl_b.matches("Alt+B".parse().unwrap()));
+
+    let left = Keymap::new().with_binding(ctrl_b, KeyAction::TogglePanel);
+    let right = Keymap::new().with_binding(ctrl_alt_b, KeyAction::TogglePanel);
+    let conflicts = find_conflicts([(PanelSide::Left, &left), (PanelSide::Right, &right)]);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].shortcut, ctrl_alt_b);
+    assert_eq!(
+        conflicts[0].to_string(),
+        "Ctrl+Alt+B triggers Left TogglePanel (Ctrl+B), Right TogglePanel"
+    );
+
+    let bottom = Keymap::new().with_binding(cmd_b, KeyAction::TogglePanel);
+    let conflicts = find_conflicts([(PanelSide::Left, &left), (PanelSide::Bottom, &bottom)]);
+    assert_eq!(conflicts.len(), 1, "{conflicts:?}");
+}
@@ -0,0 +1,199 @@
+//! Round-trips and migrations of standalone layout files.
+
+use egui_collapsible_dock::{
+    CollapsibleDockState, KeyedPanelState, LayoutFileError, LayoutFormat, PanelSide, PanelState,
+    LAYOUT_FILE_VERSION,
+};
+
+fn customized_state() -> CollapsibleDockState {
+    let mut state = CollapsibleDockState::new();
+    state.set_panel_collapsed(PanelSide::Left, true);
+    state.set_panel_size(PanelSide::Right, 420.0);
+    state.animation_duration = 0.35;
+    state
+}
+
+fn assert_same_layout(actual: &CollapsibleDockState, expected: &CollapsibleDockState) {
+    for side in [
+        PanelSide::Left,
+        PanelSide::Right,
+        PanelSide::Top,
+        PanelSide::Bottom,
+    ] {
+        assert_eq!(
+            actual.is_panel_collapsed(side),
+            expected.is_panel_collapsed(side),
+            "{side:?}"
+        );
+        assert_eq!(
+            actual.get_panel_size(side),
+            expected.get_panel_size(side),
+            "{side:?}"
+        );
+    }
+    assert_eq!(actual.animation_duration, expected.animation_duration);
+}
+
+#[test]
+fn layouts_round_trip_through_both_formats() {
+    let state = customized_state();
+    for format in [LayoutFormat::Ron, LayoutFormat::Json] {
+        let text = state.to_layout_string(format).unwrap();
+        assert!(text.contains("version"), "{format:?}: {text}");
+
+        let loaded = CollapsibleDockState::from_layout_str(&text, format).unwrap();
+        assert_same_layout(&loaded, &state);
+    }
+}
+
+#[test]
+fn unversioned_layouts_are_migrated() {
+    let state = customized_state();
+    let bare = serde_json::to_string(&state).unwrap();
+
+    let loaded = CollapsibleDockState::from_layout_str(&bare, LayoutFormat::Json).unwrap();
+    assert_same_layout(&loaded, &state);
+}
+
+#[test]
+fn newer_versions_are_rejected() {
+    let text = format!(
+        r#"{{ "version": {}, "layout": {{}} }}"#,
+        LAYOUT_FILE_VERSION + 1
+    );
+
+    let err = CollapsibleDockState::from_layout_str(&text, LayoutFormat::Json).unwrap_err();
+    assert!(
+        matches!(err, LayoutFileError::UnsupportedVersion { found, .. } if found == LAYOUT_FILE_VERSION + 1),
+        "{err}"
+    );
+}
+
+#[test]
+fn files_pick_their_format_from_the_extension() {
+    let dir = std::env::temp_dir().join(format!("egui_collapsible_dock_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let state = customized_state();
+
+    for name in ["layout.ron", "layout.json"] {
+        let path = dir.join(name);
+        state.save_to_file(&path).unwrap();
+        let loaded = CollapsibleDockState::load_from_file(&path).unwrap();
+        assert_same_layout(&loaded, &state);
+    }
+
+    let err = state.save_to_file(dir.join("layout.txt")).unwrap_err();
+    assert!(matches!(err, LayoutFileError::UnknownFormat(_)), "{err}");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn version_1_layouts_gain_pinned_panels() {
+    let text = r#"{
+        "version": 1,
+        "layout": {
+            "panels": {
+                "Left": { "collapsed": true, "size": 320.0, "min_size": 150.0, "max_size": null, "resizable": true }
+            },
+            "animation_duration": 0.2,
+            "persist_state": true
+        }
+    }"#;
+
+    let loaded = CollapsibleDockState::from_layout_str(text, LayoutFormat::Json).unwrap();
+    let left = &loaded.panels[&PanelSide::Left];
+    assert!(left.pinned);
+    assert!(left.collapsed);
+    assert_eq!(left.size, 320.0);
+}
+
+#[test]
+fn pin_state_round_trips() {
+    let mut state = customized_state();
+    state.panels.get_mut(&PanelSide::Bottom).unwrap().pinned = false;
+
+    let text = state.to_layout_string(LayoutFormat::Ron).unwrap();
+    let loaded = CollapsibleDockState::from_layout_str(&text, LayoutFormat::Ron).unwrap();
+    assert!(!loaded.panels[&PanelSide::Bottom].pinned);
+    assert!(loaded.panels[&PanelSide::Left].pinned);
+}
+
+#[test]
+fn button_order_round_trips_and_defaults_to_empty() {
+    let mut state = customized_state();
+    state.panels.get_mut(&PanelSide::Left).unwrap().button_order =
+        vec!["search".to_owned(), "files".to_owned()];
+
+    let text = state.to_layout_string(LayoutFormat::Json).unwrap();
+    let loaded = CollapsibleDockState::from_layout_str(&text, LayoutFormat::Json).unwrap();
+    assert_eq!(
+        loaded.panels[&PanelSide::Left].button_order,
+        ["search", "files"]
+    );
+
+    let version_2 = r#"(
+        version: 2,
+        layout: (
+            panels: {
+                "Left": (collapsed: false, size: 300.0, min_size: 150.0, max_size: None, resizable: true, pinned: false),
+            },
+            animation_duration: 0.2,
+            persist_state: true,
+        ),
+    )"#;
+    let loaded = CollapsibleDockState::from_layout_str(version_2, LayoutFormat::Ron).unwrap();
+    let left = &loaded.panels[&PanelSide::Left];
+    assert!(left.button_order.is_empty());
+    assert!(left.hidden_buttons.is_empty());
+    assert!(!left.pinned);
+}
+
+#[test]
+fn version_4_layouts_keep_panels_on_their_home_side() {
+    let version_4 = r#"(
+        version: 4,
+        layout: (
+            panels: {},
+            animation_duration: 0.2,
+            persist_state: true,
+        ),
+    )"#;
+    let loaded = CollapsibleDockState::from_layout_str(version_4, LayoutFormat::Ron).unwrap();
+    assert!(loaded.side_overrides.is_empty());
+
+    let mut moved = CollapsibleDockState::new();
+    moved
+        .side_overrides
+        .insert(PanelSide::Left, PanelSide::Bottom);
+    let text = moved.to_layout_string(LayoutFormat::Ron).unwrap();
+    let reloaded = CollapsibleDockState::from_layout_str(&text, LayoutFormat::Ron).unwrap();
+    assert_eq!(reloaded.side_overrides, moved.side_overrides);
+}
+
+#[test]
+fn keyed_panels_round_trip_and_default_to_empty() {
+    let mut state = customized_state();
+    state.keyed_panels.insert(
+        "outline".to_owned(),
+        KeyedPanelState {
+            side: PanelSide::Left,
+            state: PanelState {
+                collapsed: true,
+                size: 240.0,
+                ..Default::default()
+            },
+        },
+    );
+
+    let text = state.to_layout_string(LayoutFormat::Ron).unwrap();
+    let loaded = CollapsibleDockState::from_layout_str(&text, LayoutFormat::Ron).unwrap();
+    let outline = &loaded.keyed_panels["outline"];
+    assert_eq!(outline.side, PanelSide::Left);
+    assert!(outline.state.collapsed);
+    assert_eq!(outline.state.size, 240.0);
+
+    let version_5 = r#"{ "version": 5, "layout": { "panels": {}, "animation_duration": 0.2, "persist_state": true } }"#;
+    let loaded = CollapsibleDockState::from_layout_str(version_5, LayoutFormat::Json).unwrap();
+    assert!(loaded.keyed_panels.is_empty());
+}
@@ -0,0 +1,198 @@
+//! Named layout presets on `CollapsibleWorkspace`.
+
+mod common;
+
+use common::{panel, tab_names, Harness, TestWorkspace};
+use egui::Id;
+use egui_collapsible_dock::{CollapsibleWorkspace, PanelEvent, PanelSide, WorkspaceEvent};
+use egui_dock::DockState;
+
+fn test_workspace() -> TestWorkspace {
+    CollapsibleWorkspace::new(
+        Id::new("workspace"),
+        DockState::new(vec!["main.rs".to_owned()]),
+    )
+    .with_panel(panel(PanelSide::Left, "left"))
+    .with_panel(panel(PanelSide::Bottom, "bottom"))
+}
+
+fn central_tabs(workspace: &TestWorkspace) -> Vec<String> {
+    tab_names(workspace.central_dock_state())
+}
+
+#[test]
+fn presets_are_listed_in_save_order() {
+    let mut workspace = test_workspace();
+    workspace.save_preset("editing");
+    workspace.save_preset("debugging");
+    workspace.save_preset("editing");
+
+    assert_eq!(
+        workspace.preset_names().collect::<Vec<_>>(),
+        ["editing", "debugging"]
+    );
+    assert_eq!(workspace.active_preset(), Some("editing"));
+
+    assert!(workspace.delete_preset("editing").is_some());
+    assert!(workspace.delete_preset("editing").is_none());
+    assert_eq!(workspace.preset_names().collect::<Vec<_>>(), ["debugging"]);
+    assert_eq!(workspace.active_preset(), None);
+}
+
+#[test]
+fn switching_restores_panels_buttons_and_tabs() {
+    let mut harness = Harness::new();
+    let mut workspace = test_workspace();
+    harness.settle(&mut workspace);
+    workspace.save_preset("editing");
+
+    let left = workspace.panel_mut(PanelSide::Left).unwrap();
+    left.set_size(450.0);
+    left.set_active_button(1);
+    workspace
+        .panel_mut(PanelSide::Bottom)
+        .unwrap()
+        .set_collapsed(true);
+    *workspace.central_dock_state_mut() = DockState::new(vec!["debugger".to_owned()]);
+    harness.settle(&mut workspace);
+    workspace.save_preset("debugging");
+
+    assert!(workspace.switch_to_preset("editing"));
+    harness.settle(&mut workspace);
+
+    let left = workspace.panel(PanelSide::Left).unwrap();
+    assert_eq!(left.get_size(), 300.0);
+    assert_eq!(left.get_active_button(), Some(0));
+    assert!(!workspace.panel(PanelSide::Bottom).unwrap().is_collapsed());
+    assert_eq!(central_tabs(&workspace), ["main.rs"]);
+
+    assert!(workspace.switch_to_preset("debugging"));
+    harness.settle(&mut workspace);
+
+    assert_eq!(workspace.panel(PanelSide::Left).unwrap().get_size(), 450.0);
+    assert!(workspace.panel(PanelSide::Bottom).unwrap().is_collapsed());
+    assert_eq!(central_tabs(&workspace), ["debugger"]);
+    assert!(!workspace.switch_to_preset("review"));
+}
+
+fn moves(events: Vec<WorkspaceEvent>) -> Vec<WorkspaceEvent> {
+    events
+        .into_iter()
+        .filter(|(_, _, event)| matches!(event, PanelEvent::Moved { .. }))
+        .collect()
+}
+
+#[test]
+fn switching_moves_panels_back_and_reports_moved() {
+    let mut harness = Harness::new();
+    let mut workspace = test_workspace();
+    harness.settle(&mut workspace);
+    workspace.save_preset("editing");
+
+    workspace
+        .panel_mut(PanelSide::Left)
+        .unwrap()
+        .set_side(PanelSide::Bottom);
+    harness.settle(&mut workspace);
+    workspace.save_preset("swapped");
+
+    assert!(workspace.switch_to_preset("editing"));
+    let (_, events) = harness.frame(&mut workspace, Vec::new());
+    let first_moves = moves(events);
+    assert_eq!(first_moves.len(), 2, "{first_moves:?}");
+    assert!(first_moves.contains(&(
+        PanelSide::Left,
+        None,
+        PanelEvent::Moved {
+            from: PanelSide::Bottom,
+            to: PanelSide::Left,
+        }
+    )));
+    assert!(first_moves.contains(&(
+        PanelSide::Bottom,
+        None,
+        PanelEvent::Moved {
+            from: PanelSide::Left,
+            to: PanelSide::Bottom,
+        }
+    )));
+    harness.settle(&mut workspace);
+    let left = workspace.panel(PanelSide::Left).unwrap();
+    assert_eq!(left.side(), PanelSide::Left);
+    assert_eq!(tab_names(left.dock_state()), ["left"]);
+
+    // Switching to the preset that was saved with the panels swapped moves them again
+    assert!(workspace.switch_to_preset("swapped"));
+    let (_, events) = harness.settle(&mut workspace);
+    assert_eq!(moves(events).len(), 2);
+    assert_eq!(
+        tab_names(workspace.panel(PanelSide::Bottom).unwrap().dock_state()),
+        ["left"]
+    );
+}
+
+#[test]
+fn switching_reports_the_size_change() {
+    let mut harness = Harness::new();
+    let mut workspace = test_workspace();
+    harness.settle(&mut workspace);
+    workspace.save_preset("editing");
+
+    workspace
+        .panel_mut(PanelSide::Left)
+        .unwrap()
+        .set_size(450.0);
+    harness.settle(&mut workspace);
+    workspace.save_preset("wide");
+
+    assert!(workspace.switch_to_preset("editing"));
+    let (_, events) = harness.settle(&mut workspace);
+    let resizes: Vec<_> = events
+        .iter()
+        .filter(|(_, _, event)| matches!(event, PanelEvent::Resized { .. }))
+        .collect();
+    assert_eq!(
+        resizes,
+        [&(
+            PanelSide::Left,
+            None,
+            PanelEvent::Resized {
+                old: 450.0,
+                new: 300.0,
+            }
+        )]
+    );
+    assert!(moves(events).is_empty());
+}
+
+#[test]
+fn switching_moves_keyed_panels_and_names_them() {
+    let mut harness = Harness::new();
+    let mut workspace =
+        test_workspace().with_panel(panel(PanelSide::Left, "outline").with_panel_id("outline"));
+    harness.settle(&mut workspace);
+    workspace.save_preset("editing");
+
+    workspace
+        .panel_by_id_mut("outline")
+        .unwrap()
+        .set_side(PanelSide::Right);
+    harness.settle(&mut workspace);
+    assert_eq!(workspace.panels_on(PanelSide::Right).len(), 1);
+
+    assert!(workspace.switch_to_preset("editing"));
+    let (_, events) = harness.settle(&mut workspace);
+    assert_eq!(
+        moves(events),
+        [(
+            PanelSide::Left,
+            Some("outline".to_owned()),
+            PanelEvent::Moved {
+                from: PanelSide::Right,
+                to: PanelSide::Left,
+            }
+        )]
+    );
+    assert!(workspace.panels_on(PanelSide::Right).is_empty());
+    assert_eq!(workspace.panels_on(PanelSide::Left).len(), 2);
+}
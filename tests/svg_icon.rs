@@ -0,0 +1,127 @@
+//! SVG icons behind the `svg` feature: loading errors, tinting and the texture cache.
+
+#![cfg(feature = "svg")]
+
+use egui::{Color32, Context, LayerId, Painter, Pos2, Rect, Shape, Vec2};
+use egui_collapsible_dock::{Icon, IconRegistry, SvgIcon, SvgIconError};
+
+const RED_SQUARE: &[u8] = br##"<svg xmlns="http://www.w3.org/2000/svg" width="16" height="16">
+    <rect width="16" height="16" fill="#ff0000"/>
+</svg>"##;
+
+/// Runs one frame of `ctx` painting through `paint`, returning the tint of every image mesh.
+fn paint_frame(ctx: &Context, paint: impl FnOnce(&Painter)) -> Vec<Color32> {
+    let mut paint = Some(paint);
+    let output = ctx.run(Default::default(), |ctx| {
+        let painter = Painter::new(ctx.clone(), LayerId::background(), Rect::EVERYTHING);
+        if let Some(paint) = paint.take() {
+            paint(&painter);
+        }
+    });
+    output
+        .shapes
+        .iter()
+        .filter_map(|clipped| match &clipped.shape {
+            Shape::Mesh(mesh) => mesh.vertices.first().map(|vertex| vertex.color),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Names of the textures currently allocated for the icon called `name`.
+fn icon_textures(ctx: &Context, name: &str) -> Vec<String> {
+    let prefix = format!("{name}@");
+    let mut names: Vec<String> = ctx
+        .tex_manager()
+        .read()
+        .allocated()
+        .filter(|(_, meta)| meta.name.starts_with(&prefix))
+        .map(|(_, meta)| meta.name.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+fn square(size: f32) -> Rect {
+    Rect::from_min_size(Pos2::ZERO, Vec2::splat(size))
+}
+
+#[test]
+fn invalid_svg_is_a_parse_error_and_registers_nothing() {
+    assert!(matches!(
+        SvgIcon::from_bytes("broken", b"not an svg"),
+        Err(SvgIconError::Parse(_))
+    ));
+
+    let mut registry = IconRegistry::new();
+    let result = registry.register_svg_bytes("svg:Broken", b"<svg");
+    assert!(matches!(result, Err(SvgIconError::Parse(_))));
+    assert!(!registry.contains("svg:Broken"));
+}
+
+#[test]
+fn missing_svg_file_is_an_io_error() {
+    let mut registry = IconRegistry::new();
+    let result = registry.register_svg_file("svg:Missing", "does/not/exist.svg");
+    assert!(matches!(result, Err(SvgIconError::Io(_))));
+    assert!(!registry.contains("svg:Missing"));
+}
+
+#[test]
+fn textures_are_cached_per_pixel_size() {
+    let ctx = Context::default();
+    let icon = SvgIcon::from_bytes("square", RED_SQUARE).unwrap();
+
+    paint_frame(&ctx, |painter| {
+        icon.paint(painter, square(14.0), Color32::WHITE)
+    });
+    paint_frame(&ctx, |painter| {
+        icon.paint(painter, square(14.0), Color32::WHITE)
+    });
+    assert_eq!(icon_textures(&ctx, "square"), ["square@14x14"]);
+
+    paint_frame(&ctx, |painter| {
+        icon.paint(painter, square(28.0), Color32::WHITE)
+    });
+    assert_eq!(
+        icon_textures(&ctx, "square"),
+        ["square@14x14", "square@28x28"]
+    );
+
+    paint_frame(&ctx, |painter| {
+        icon.paint(painter, square(14.0), Color32::WHITE)
+    });
+    paint_frame(&ctx, |painter| {
+        icon.paint(painter, square(28.0), Color32::WHITE)
+    });
+    assert_eq!(
+        icon_textures(&ctx, "square").len(),
+        2,
+        "both sizes come from the cache"
+    );
+}
+
+#[test]
+fn registered_svgs_tint_unless_they_keep_their_colors() {
+    let mut registry = IconRegistry::new();
+    registry.register_svg_bytes("svg:Mask", RED_SQUARE).unwrap();
+    registry.register_svg(
+        "svg:Colored",
+        SvgIcon::from_bytes("colored", RED_SQUARE)
+            .unwrap()
+            .with_original_colors(),
+    );
+    assert!(matches!(registry.get("svg:Colored"), Some(Icon::Svg(_))));
+
+    let ctx = Context::default();
+    let tint = Color32::from_rgb(0, 128, 255);
+    let mask = paint_frame(&ctx, |painter| {
+        assert!(registry.paint(painter, "svg:Mask", square(14.0), tint));
+    });
+    assert_eq!(mask, [tint]);
+
+    let colored = paint_frame(&ctx, |painter| {
+        assert!(registry.paint(painter, "svg:Colored", square(14.0), tint));
+    });
+    assert_eq!(colored, [Color32::WHITE]);
+}
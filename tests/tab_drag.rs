@@ -0,0 +1,250 @@
+//! Moving tabs between the `DockState`s of a `CollapsibleWorkspace`.
+
+mod common;
+
+use common::{
+    panel_with_tabs, tab_names, Harness, Scene, TabDragScene, TestViewer, TestWorkspace,
+    SCREEN_SIZE,
+};
+use egui::{Context, Id, Pos2, Rect, Vec2};
+use egui_collapsible_dock::{CollapsibleWorkspace, DockTarget, PanelSide, WorkspaceEvent};
+use egui_dock::DockState;
+
+fn test_workspace() -> TestWorkspace {
+    CollapsibleWorkspace::new(
+        Id::new("workspace"),
+        DockState::new(vec!["main.rs".to_owned()]),
+    )
+    .with_panel(panel_with_tabs(
+        PanelSide::Left,
+        "left",
+        &["Explorer", "Outline"],
+    ))
+    .with_panel(panel_with_tabs(PanelSide::Bottom, "bottom", &["Terminal"]))
+}
+
+fn tabs(workspace: &TestWorkspace, target: DockTarget) -> Vec<String> {
+    tab_names(workspace.dock_state(&target).expect("target exists"))
+}
+
+/// Centre of the tab bar header titled `title`.
+fn tab_header(harness: &mut Harness, scene: &mut TabDragScene, title: &str) -> Pos2 {
+    let texts = harness.painted_text_rects(scene);
+    texts
+        .iter()
+        .find(|(text, _)| text == title)
+        .map(|(_, rect)| rect.center())
+        .unwrap_or_else(|| panic!("no tab titled {title}: {texts:?}"))
+}
+
+const LEFT: DockTarget = DockTarget::Panel(PanelSide::Left);
+const BOTTOM: DockTarget = DockTarget::Panel(PanelSide::Bottom);
+
+#[test]
+fn tabs_move_between_panels_and_notify_the_viewer() {
+    let mut workspace = test_workspace();
+    let mut viewer = TestViewer::default();
+
+    assert!(workspace.move_tab(&mut viewer, &"Outline".to_owned(), LEFT, BOTTOM));
+    assert!(workspace.move_tab(
+        &mut viewer,
+        &"Terminal".to_owned(),
+        BOTTOM,
+        DockTarget::Central
+    ));
+
+    assert_eq!(tabs(&workspace, LEFT), ["Explorer"]);
+    assert_eq!(tabs(&workspace, BOTTOM), ["Outline"]);
+    assert_eq!(
+        tabs(&workspace, DockTarget::Central),
+        ["main.rs", "Terminal"]
+    );
+    assert_eq!(
+        viewer.moves,
+        [
+            ("Outline".to_owned(), LEFT, BOTTOM),
+            ("Terminal".to_owned(), BOTTOM, DockTarget::Central),
+        ]
+    );
+}
+
+#[test]
+fn invalid_moves_leave_the_layout_untouched() {
+    let mut workspace = test_workspace();
+    let mut viewer = TestViewer::default();
+    let right = DockTarget::Panel(PanelSide::Right);
+
+    assert!(!workspace.move_tab(&mut viewer, &"Missing".to_owned(), LEFT, BOTTOM));
+    assert!(!workspace.move_tab(&mut viewer, &"Explorer".to_owned(), LEFT, LEFT));
+    assert!(!workspace.move_tab(&mut viewer, &"Explorer".to_owned(), LEFT, right));
+
+    assert_eq!(tabs(&workspace, LEFT), ["Explorer", "Outline"]);
+    assert!(viewer.moves.is_empty());
+}
+
+#[test]
+fn moving_into_a_collapsed_panel_keeps_it_collapsed() {
+    let mut workspace = test_workspace();
+    let mut viewer = TestViewer::default();
+    workspace
+        .panel_mut(PanelSide::Bottom)
+        .unwrap()
+        .set_collapsed(true);
+
+    let mut harness = Harness::new();
+    let mut scene = TabDragScene::new(workspace);
+    harness.frame(&mut scene, Vec::new());
+
+    assert!(scene
+        .workspace
+        .move_tab(&mut viewer, &"Explorer".to_owned(), LEFT, BOTTOM));
+    harness.settle(&mut scene);
+
+    let workspace = &scene.workspace;
+    assert!(workspace.panel(PanelSide::Bottom).unwrap().is_collapsed());
+    assert_eq!(tabs(workspace, BOTTOM), ["Terminal", "Explorer"]);
+}
+
+#[test]
+fn keyed_panels_are_targets_of_their_own() {
+    let mut workspace = test_workspace().with_panel(
+        panel_with_tabs(PanelSide::Left, "outline", &["Symbols"]).with_panel_id("outline"),
+    );
+    let mut viewer = TestViewer::default();
+    let outline = DockTarget::KeyedPanel("outline".to_owned());
+
+    assert!(workspace.move_tab(&mut viewer, &"Explorer".to_owned(), LEFT, outline.clone()));
+    assert_eq!(tabs(&workspace, LEFT), ["Outline"]);
+    assert_eq!(tabs(&workspace, outline.clone()), ["Symbols", "Explorer"]);
+    assert_eq!(
+        workspace.panel_by_id("outline").unwrap().dock_target(),
+        outline
+    );
+    assert!(workspace
+        .dock_state(&DockTarget::KeyedPanel("missing".to_owned()))
+        .is_none());
+}
+
+#[test]
+fn dragging_a_tab_header_onto_another_panel_moves_it() {
+    let mut harness = Harness::new();
+    let mut scene = TabDragScene::new(test_workspace());
+    harness.settle(&mut scene);
+
+    // Outline is not the active tab of the left panel.
+    let outline = tab_header(&mut harness, &mut scene, "Outline");
+    let terminal = scene.viewer.content_rect("Terminal");
+    harness.drag(&mut scene, outline, terminal.center());
+
+    assert_eq!(tabs(&scene.workspace, LEFT), ["Explorer"]);
+    assert_eq!(tabs(&scene.workspace, BOTTOM), ["Terminal", "Outline"]);
+    assert_eq!(scene.viewer.moves, [("Outline".to_owned(), LEFT, BOTTOM)]);
+}
+
+#[test]
+fn dragging_one_of_two_equal_tabs_moves_that_one() {
+    let workspace = CollapsibleWorkspace::new(Id::new("workspace"), DockState::new(vec![]))
+        .with_panel(panel_with_tabs(
+            PanelSide::Left,
+            "left",
+            &["Console", "Explorer", "Console"],
+        ))
+        .with_panel(panel_with_tabs(PanelSide::Bottom, "bottom", &["Terminal"]));
+    let mut harness = Harness::new();
+    let mut scene = TabDragScene::new(workspace);
+    harness.settle(&mut scene);
+
+    // Tab headers, not the active tab's content label.
+    let content_top = scene.viewer.content_rect("Console").top();
+    let consoles: Vec<Pos2> = harness
+        .painted_text_rects(&mut scene)
+        .into_iter()
+        .filter(|(text, rect)| text == "Console" && rect.center().y < content_top)
+        .map(|(_, rect)| rect.center())
+        .collect();
+    assert_eq!(consoles.len(), 2);
+    let terminal = scene.viewer.content_rect("Terminal");
+    harness.drag(&mut scene, consoles[1], terminal.center());
+
+    assert_eq!(tabs(&scene.workspace, LEFT), ["Console", "Explorer"]);
+    assert_eq!(tabs(&scene.workspace, BOTTOM), ["Terminal", "Console"]);
+}
+
+#[test]
+fn dragging_a_tab_header_onto_a_collapsed_strip_keeps_it_collapsed() {
+    let mut workspace = test_workspace();
+    workspace
+        .panel_mut(PanelSide::Bottom)
+        .unwrap()
+        .set_collapsed(true);
+    let mut harness = Harness::new();
+    let mut scene = TabDragScene::new(workspace);
+    harness.settle(&mut scene);
+
+    let explorer = tab_header(&mut harness, &mut scene, "Explorer");
+    let strip = Pos2::new(SCREEN_SIZE.x / 2.0, SCREEN_SIZE.y - 5.0);
+    harness.drag(&mut scene, explorer, strip);
+
+    let workspace = &scene.workspace;
+    assert!(workspace.panel(PanelSide::Bottom).unwrap().is_collapsed());
+    assert_eq!(tabs(workspace, LEFT), ["Outline"]);
+    assert_eq!(tabs(workspace, BOTTOM), ["Terminal", "Explorer"]);
+}
+
+#[test]
+fn drags_outside_the_tab_bar_or_drop_zones_move_nothing() {
+    let mut harness = Harness::new();
+    let mut scene = TabDragScene::new(test_workspace());
+    harness.settle(&mut scene);
+
+    let explorer = tab_header(&mut harness, &mut scene, "Explorer");
+    let body = scene.viewer.content_rect("Explorer");
+    let terminal = scene.viewer.content_rect("Terminal");
+    // From the tab content rather than its header.
+    harness.drag(&mut scene, body.center(), terminal.center());
+    // From the header, released back inside its own panel.
+    harness.drag(&mut scene, explorer, body.center());
+
+    assert_eq!(tabs(&scene.workspace, LEFT), ["Explorer", "Outline"]);
+    assert_eq!(tabs(&scene.workspace, BOTTOM), ["Terminal"]);
+    assert!(scene.viewer.moves.is_empty());
+}
+
+/// A tab-drag workspace with a window floating over it, standing in for one
+/// of egui_dock's floating windows.
+struct WindowOver {
+    scene: TabDragScene,
+    window: Pos2,
+}
+
+impl Scene for WindowOver {
+    type Event = WorkspaceEvent;
+
+    fn show(&mut self, ctx: &Context) -> (Option<Rect>, Vec<WorkspaceEvent>) {
+        let result = self.scene.show(ctx);
+        egui::Window::new("floating")
+            .fixed_pos(self.window)
+            .fixed_size(Vec2::splat(100.0))
+            .show(ctx, |ui| ui.label("window"));
+        result
+    }
+}
+
+#[test]
+fn drops_over_a_window_are_left_to_the_window() {
+    let mut harness = Harness::new();
+    let mut scene = TabDragScene::new(test_workspace());
+    harness.settle(&mut scene);
+    let outline = tab_header(&mut harness, &mut scene, "Outline");
+    let terminal = scene.viewer.content_rect("Terminal").center();
+
+    let mut scene = WindowOver {
+        scene,
+        window: terminal - Vec2::splat(20.0),
+    };
+    harness.settle(&mut scene);
+    harness.drag(&mut scene, outline, terminal);
+
+    assert_eq!(tabs(&scene.scene.workspace, LEFT), ["Explorer", "Outline"]);
+    assert!(scene.scene.viewer.moves.is_empty());
+}
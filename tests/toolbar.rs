@@ -0,0 +1,246 @@
+//! Headless tests for `CollapsibleToolbar`, which works without egui_dock.
+
+mod common;
+
+use common::{pointer_event, Harness, ToolbarScene, TOOLBAR_ID};
+use egui::{Event, Id, Pos2, Rect, Vec2};
+use egui_collapsible_dock::{CollapsibleToolbar, Easing, PanelSide, ToolbarState};
+
+fn test_toolbar(side: PanelSide) -> ToolbarScene {
+    ToolbarScene::new(CollapsibleToolbar::new(
+        side,
+        vec!["Files".to_owned(), "Search".to_owned()],
+    ))
+}
+
+/// Runs enough frames for any animation to finish, returning the toolbar rect.
+fn settle(harness: &mut Harness, scene: &mut ToolbarScene) -> Rect {
+    harness
+        .settle(scene)
+        .0
+        .expect("toolbar always returns a response")
+}
+
+/// Opens the context menu of the tab at `tab_pos` and clicks its first item.
+fn close_via_context_menu(harness: &mut Harness, scene: &mut ToolbarScene, tab_pos: Pos2) {
+    let button = egui::PointerButton::Secondary;
+    harness.frame(scene, vec![Event::PointerMoved(tab_pos)]);
+    harness.frame(scene, vec![pointer_event(tab_pos, button, true)]);
+    harness.frame(scene, vec![pointer_event(tab_pos, button, false)]);
+    harness.settle(scene);
+    harness.click(scene, tab_pos + Vec2::new(20.0, 12.0));
+}
+
+/// Expands the toolbar with the first tab selected.
+fn expand(harness: &mut Harness, scene: &mut ToolbarScene) -> Rect {
+    scene
+        .toolbar
+        .modify_state(&harness.ctx, Id::new(TOOLBAR_ID), |state| {
+            state.selected_tab = Some(0);
+            state.is_expanded = true;
+        });
+    settle(harness, scene)
+}
+
+fn tabs(harness: &Harness, scene: &ToolbarScene) -> Vec<String> {
+    scene.toolbar.state(&harness.ctx, Id::new(TOOLBAR_ID)).tabs
+}
+
+#[test]
+fn toolbar_starts_collapsed_on_the_shared_panel_side() {
+    let mut harness = Harness::new();
+    let rect = settle(&mut harness, &mut test_toolbar(PanelSide::Left));
+
+    assert_eq!(rect.left(), 0.0);
+    assert!(rect.width() < 40.0, "collapsed width {}", rect.width());
+}
+
+#[test]
+fn clicking_a_collapsed_tab_expands_the_toolbar() {
+    let mut harness = Harness::new();
+    let mut toolbar = test_toolbar(PanelSide::Left);
+    let rect = settle(&mut harness, &mut toolbar);
+
+    harness.click(&mut toolbar, Pos2::new(rect.center().x, rect.top() + 8.0));
+    let rect = settle(&mut harness, &mut toolbar);
+
+    assert!(rect.width() >= 200.0, "expanded width {}", rect.width());
+}
+
+#[test]
+fn removing_tabs_fixes_up_the_selection() {
+    let mut state = ToolbarState {
+        tabs: vec!["a", "b", "c"],
+        selected_tab: Some(2),
+        is_expanded: true,
+    };
+
+    assert_eq!(state.remove_tab(0), Some("a"));
+    assert_eq!(state.selected_tab, Some(1), "selection follows its tab");
+
+    assert_eq!(state.remove_tab(1), Some("c"));
+    assert_eq!(state.selected_tab, Some(0), "neighbour becomes selected");
+
+    assert_eq!(state.remove_tab(5), None);
+    assert_eq!(state.remove_tab(0), Some("b"));
+    assert_eq!(state.selected_tab, None);
+    assert!(!state.is_expanded, "empty toolbar collapses");
+}
+
+#[test]
+fn inserting_tabs_keeps_the_selected_tab() {
+    let mut state = ToolbarState {
+        tabs: vec!["a", "b"],
+        selected_tab: Some(1),
+        is_expanded: true,
+    };
+
+    state.insert_tab(0, "new");
+    state.add_tab("last");
+
+    assert_eq!(state.tabs, ["new", "a", "b", "last"]);
+    assert_eq!(state.selected_tab, Some(2));
+}
+
+#[test]
+fn programmatic_tab_changes_survive_frames() {
+    let mut harness = Harness::new();
+    let mut toolbar = test_toolbar(PanelSide::Left);
+    settle(&mut harness, &mut toolbar);
+
+    toolbar
+        .toolbar
+        .add_tab(&harness.ctx, TOOLBAR_ID, "Console".to_owned());
+    toolbar
+        .toolbar
+        .insert_tab(&harness.ctx, TOOLBAR_ID, 0, "Outline".to_owned());
+    settle(&mut harness, &mut toolbar);
+    assert_eq!(
+        tabs(&harness, &toolbar),
+        ["Outline", "Files", "Search", "Console"]
+    );
+
+    toolbar.toolbar.remove_tab(&harness.ctx, TOOLBAR_ID, 0);
+    toolbar.toolbar.remove_tab(&harness.ctx, TOOLBAR_ID, 0);
+    toolbar.toolbar.remove_tab(&harness.ctx, TOOLBAR_ID, 0);
+    toolbar.toolbar.remove_tab(&harness.ctx, TOOLBAR_ID, 0);
+    settle(&mut harness, &mut toolbar);
+    assert!(
+        tabs(&harness, &toolbar).is_empty(),
+        "closed tabs stay closed"
+    );
+}
+
+#[test]
+fn context_menu_closes_tab() {
+    let mut harness = Harness::new();
+    let mut toolbar = test_toolbar(PanelSide::Left);
+    let rect = expand(&mut harness, &mut toolbar);
+
+    close_via_context_menu(
+        &mut harness,
+        &mut toolbar,
+        rect.left_top() + Vec2::new(20.0, 14.0),
+    );
+
+    assert_eq!(toolbar.viewer.close_requests, ["Files"]);
+    assert_eq!(tabs(&harness, &toolbar), ["Search"]);
+}
+
+#[test]
+fn on_close_can_veto_closing() {
+    let mut harness = Harness::new();
+    let mut toolbar = test_toolbar(PanelSide::Left);
+    toolbar.viewer.veto_close = true;
+    let rect = expand(&mut harness, &mut toolbar);
+
+    close_via_context_menu(
+        &mut harness,
+        &mut toolbar,
+        rect.left_top() + Vec2::new(20.0, 14.0),
+    );
+
+    assert_eq!(toolbar.viewer.close_requests, ["Files"]);
+    assert_eq!(tabs(&harness, &toolbar), ["Files", "Search"]);
+}
+
+#[test]
+fn zero_duration_expands_in_one_frame() {
+    let mut harness = Harness::new();
+    let mut toolbar = ToolbarScene::new(
+        CollapsibleToolbar::new(PanelSide::Left, vec!["Files".to_owned()]).animation_duration(0.0),
+    );
+    settle(&mut harness, &mut toolbar);
+
+    toolbar
+        .toolbar
+        .modify_state(&harness.ctx, Id::new(TOOLBAR_ID), |state| {
+            state.is_expanded = true;
+        });
+    let rect = harness.frame(&mut toolbar, Vec::new()).0.unwrap();
+    assert_eq!(rect.width(), 200.0);
+}
+
+#[test]
+fn expansion_follows_the_duration_and_easing() {
+    let mut harness = Harness::new();
+    let mut toolbar = ToolbarScene::new(
+        CollapsibleToolbar::new(PanelSide::Left, vec!["Files".to_owned()])
+            .animation_duration(0.5)
+            .easing(Easing::Linear),
+    );
+    let collapsed = settle(&mut harness, &mut toolbar).width();
+
+    toolbar
+        .toolbar
+        .modify_state(&harness.ctx, Id::new(TOOLBAR_ID), |state| {
+            state.is_expanded = true;
+        });
+    // 15 frames of 1/60 s are half of the 0.5 s animation.
+    let widths: Vec<f32> = (0..16)
+        .map(|_| harness.frame(&mut toolbar, Vec::new()).0.unwrap().width())
+        .collect();
+    assert!(
+        widths.windows(2).all(|pair| pair[0] <= pair[1]),
+        "{widths:?}"
+    );
+    let halfway = collapsed + (200.0 - collapsed) / 2.0;
+    assert!((widths[15] - halfway).abs() < 10.0, "{widths:?}");
+    assert_eq!(settle(&mut harness, &mut toolbar).width(), 200.0);
+}
+
+#[test]
+fn expanded_toolbar_resizes_below_min_size() {
+    let mut harness = Harness::new();
+    let mut toolbar = test_toolbar(PanelSide::Left);
+    let rect = expand(&mut harness, &mut toolbar);
+
+    harness.drag(
+        &mut toolbar,
+        rect.right_center(),
+        rect.right_center() - Vec2::new(80.0, 0.0),
+    );
+
+    let rect = settle(&mut harness, &mut toolbar);
+    assert!((rect.width() - 120.0).abs() < 2.0, "width {}", rect.width());
+}
+
+#[test]
+fn collapsed_tabs_shorten_non_ascii_titles_by_characters() {
+    for side in [PanelSide::Left, PanelSide::Bottom] {
+        let mut harness = Harness::new();
+        let mut toolbar = ToolbarScene::new(CollapsibleToolbar::new(
+            side,
+            vec!["控制台".to_owned(), "É".to_owned(), "🔍 搜索".to_owned()],
+        ));
+        settle(&mut harness, &mut toolbar);
+
+        let texts = harness.painted_texts(&mut toolbar);
+        for expected in ["控制", "É", "🔍"] {
+            assert!(
+                texts.iter().any(|text| text == expected),
+                "{side:?}: {texts:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,477 @@
+//! Panel placement in `CollapsibleWorkspace`.
+
+mod common;
+
+use common::{
+    panel, panel_with_tabs, tab_names, Harness, Scene, TestPanel, TestViewer, TestWorkspace,
+    WorkspaceScene, ALL_SIDES, SCREEN_SIZE,
+};
+use egui::{Id, Key, Modifiers, Vec2};
+use egui_collapsible_dock::{
+    CollapsibleDockState, CollapsibleWorkspace, CornerOwnership, KeyAction, Keymap, PanelEvent,
+    PanelKey, PanelSide,
+};
+use egui_dock::DockState;
+
+fn tabs_at(workspace: &TestWorkspace, side: PanelSide) -> Vec<String> {
+    workspace
+        .panel(side)
+        .map(|panel| {
+            assert_eq!(panel.side(), side, "panels sit in the slot of their side");
+            tab_names(panel.dock_state())
+        })
+        .unwrap_or_default()
+}
+
+#[test]
+fn moved_panels_change_slots_and_swap_with_occupants() {
+    let mut harness = Harness::new();
+    let mut workspace = CollapsibleWorkspace::new(Id::new("workspace"), DockState::new(vec![]))
+        .with_panel(panel(PanelSide::Left, "explorer"))
+        .with_panel(panel(PanelSide::Bottom, "terminal"));
+
+    workspace
+        .panel_mut(PanelSide::Left)
+        .unwrap()
+        .set_side(PanelSide::Right);
+    harness.frame(&mut workspace, Vec::new());
+    assert_eq!(tabs_at(&workspace, PanelSide::Right), ["explorer"]);
+    assert!(workspace.panel(PanelSide::Left).is_none());
+
+    workspace
+        .panel_mut(PanelSide::Right)
+        .unwrap()
+        .set_side(PanelSide::Bottom);
+    harness.frame(&mut workspace, Vec::new());
+    assert_eq!(tabs_at(&workspace, PanelSide::Bottom), ["explorer"]);
+    assert_eq!(tabs_at(&workspace, PanelSide::Right), ["terminal"]);
+}
+
+#[test]
+fn moved_and_displaced_panels_both_report_moved() {
+    let mut harness = Harness::new();
+    let mut workspace = CollapsibleWorkspace::new(Id::new("workspace"), DockState::new(vec![]))
+        .with_panel(panel(PanelSide::Left, "explorer"))
+        .with_panel(panel(PanelSide::Bottom, "terminal"));
+    harness.settle(&mut workspace);
+
+    workspace
+        .panel_mut(PanelSide::Left)
+        .unwrap()
+        .set_side(PanelSide::Bottom);
+    let (_, events) = harness.frame(&mut workspace, Vec::new());
+    let moves: Vec<_> = events
+        .into_iter()
+        .filter(|(_, _, event)| matches!(event, PanelEvent::Moved { .. }))
+        .collect();
+    assert_eq!(moves.len(), 2, "{moves:?}");
+    assert!(moves.contains(&(
+        PanelSide::Bottom,
+        None,
+        PanelEvent::Moved {
+            from: PanelSide::Left,
+            to: PanelSide::Bottom,
+        }
+    )));
+    assert!(moves.contains(&(
+        PanelSide::Left,
+        None,
+        PanelEvent::Moved {
+            from: PanelSide::Bottom,
+            to: PanelSide::Left,
+        }
+    )));
+}
+
+#[test]
+fn restored_side_overrides_move_panels_back() {
+    let mut harness = Harness::new();
+    let workspace = || {
+        CollapsibleWorkspace::new(Id::new("workspace"), DockState::new(vec![]))
+            .with_panel(panel(PanelSide::Left, "explorer"))
+            .with_panel(panel(PanelSide::Bottom, "terminal"))
+    };
+
+    let mut original = workspace();
+    original
+        .panel_mut(PanelSide::Left)
+        .unwrap()
+        .set_side(PanelSide::Bottom);
+    harness.frame(&mut original, Vec::new());
+    let state = original.state();
+    assert_eq!(state.side_overrides[&PanelSide::Left], PanelSide::Bottom);
+    assert_eq!(state.side_overrides[&PanelSide::Bottom], PanelSide::Left);
+
+    let mut restored = workspace();
+    restored.restore_state(&state);
+    assert_eq!(tabs_at(&restored, PanelSide::Bottom), ["explorer"]);
+    assert_eq!(tabs_at(&restored, PanelSide::Left), ["terminal"]);
+}
+
+#[test]
+fn the_workspace_is_the_only_owner_of_persisted_panel_state() {
+    let mut harness = Harness::new();
+    // State that a standalone "explorer" panel saved earlier must not override the workspace
+    let mut stale = CollapsibleDockState::new();
+    stale.set_panel_collapsed(PanelSide::Left, true);
+    stale.save_to_memory(&harness.ctx, Id::new("explorer"));
+
+    let mut workspace = CollapsibleWorkspace::new(Id::new("workspace"), DockState::new(vec![]))
+        .with_panel(panel(PanelSide::Left, "explorer"))
+        .with_panel(panel(PanelSide::Bottom, "terminal"));
+    harness.settle(&mut workspace);
+    assert!(!workspace.panel(PanelSide::Left).unwrap().is_collapsed());
+
+    workspace
+        .panel_mut(PanelSide::Left)
+        .unwrap()
+        .set_side(PanelSide::Bottom);
+    harness.settle(&mut workspace);
+    let saved = CollapsibleDockState::try_load_from_memory(&harness.ctx, Id::new("workspace"));
+    assert_eq!(
+        saved.unwrap().side_overrides[&PanelSide::Left],
+        PanelSide::Bottom
+    );
+    assert!(
+        CollapsibleDockState::try_load_from_memory(&harness.ctx, Id::new("terminal")).is_none(),
+        "panels in a workspace do not save their own state"
+    );
+
+    let mut removed = workspace.remove_panel(PanelSide::Left).unwrap();
+    assert_eq!(removed.home_side(), PanelSide::Bottom);
+    harness.settle(&mut removed);
+    assert!(
+        CollapsibleDockState::try_load_from_memory(&harness.ctx, Id::new("terminal")).is_some(),
+        "removed panels save their own state again"
+    );
+}
+
+fn keyed_panel(side: PanelSide, name: &str) -> TestPanel {
+    panel(side, name).with_panel_id(name)
+}
+
+#[test]
+fn keyed_panels_share_a_side_with_their_own_state() {
+    let mut harness = Harness::new();
+    let workspace = || {
+        CollapsibleWorkspace::new(Id::new("workspace"), DockState::new(vec![]))
+            .with_panel(panel(PanelSide::Left, "explorer"))
+            .with_panel(keyed_panel(PanelSide::Left, "outline"))
+            .with_panel(keyed_panel(PanelSide::Left, "timeline"))
+    };
+
+    let mut original = workspace();
+    assert_eq!(original.panels_on(PanelSide::Left).len(), 3);
+    original.panel_by_id_mut("outline").unwrap().set_size(220.0);
+    original
+        .panel_by_id_mut("timeline")
+        .unwrap()
+        .set_collapsed(true);
+    harness.frame(&mut original, Vec::new());
+    harness.frame(&mut original, Vec::new());
+
+    let state = original.state();
+    assert_eq!(state.keyed_panels["outline"].state.size, 220.0);
+    assert!(state.keyed_panels["timeline"].state.collapsed);
+    assert!(!state.panels[&PanelSide::Left].collapsed);
+
+    let mut restored = workspace();
+    restored.restore_state(&state);
+    assert_eq!(restored.panel_by_id("outline").unwrap().get_size(), 220.0);
+    assert!(restored.panel_by_id("timeline").unwrap().is_collapsed());
+    assert!(!restored.panel(PanelSide::Left).unwrap().is_collapsed());
+}
+
+#[test]
+fn keyed_panel_state_is_addressed_by_panel_id() {
+    let mut outline = keyed_panel(PanelSide::Left, "outline");
+    outline.set_size(220.0);
+    outline.set_collapsed(true);
+    assert_eq!(outline.panel_key(), PanelKey::Id("outline".to_owned()));
+
+    // The keyed panel never writes the slot of its side, which belongs to the unkeyed panel
+    let mut state = CollapsibleDockState::new();
+    panel(PanelSide::Left, "explorer").write_state(&mut state);
+    outline.write_state(&mut state);
+    assert!(!state.is_panel_collapsed(PanelSide::Left));
+    assert_ne!(state.get_panel_size(PanelSide::Left), 220.0);
+    assert!(state.is_panel_collapsed("outline"));
+    assert_eq!(state.get_panel_size("outline"), 220.0);
+
+    state.set_panel_size("outline", 260.0);
+    state.set_panel_collapsed("outline", false);
+    state.set_panel_collapsed(PanelSide::Left, true);
+    let mut restored = keyed_panel(PanelSide::Left, "outline");
+    restored.restore_state(&state);
+    assert_eq!(restored.get_size(), 260.0);
+    assert!(!restored.is_collapsed());
+}
+
+#[test]
+fn keyed_panels_replace_by_id_and_move_without_swapping() {
+    let mut harness = Harness::new();
+    let mut workspace = CollapsibleWorkspace::new(Id::new("workspace"), DockState::new(vec![]))
+        .with_panel(panel(PanelSide::Left, "explorer"))
+        .with_panel(panel(PanelSide::Bottom, "terminal"))
+        .with_panel(keyed_panel(PanelSide::Left, "outline"));
+
+    let replaced = workspace.set_panel(keyed_panel(PanelSide::Left, "outline"));
+    assert!(replaced.is_some());
+    assert_eq!(workspace.panels_on(PanelSide::Left).len(), 2);
+
+    workspace
+        .panel_by_id_mut("outline")
+        .unwrap()
+        .set_side(PanelSide::Bottom);
+    harness.frame(&mut workspace, Vec::new());
+    let bottom: Vec<_> = workspace
+        .panels_on(PanelSide::Bottom)
+        .iter()
+        .map(|panel| panel.dock_state().iter_all_tabs().next().unwrap().1.clone())
+        .collect();
+    assert_eq!(bottom, ["terminal", "outline"]);
+    assert_eq!(tabs_at(&workspace, PanelSide::Left), ["explorer"]);
+    assert_eq!(
+        workspace.state().keyed_panels["outline"].side,
+        PanelSide::Bottom
+    );
+}
+
+#[test]
+fn workspace_events_name_the_keyed_panel() {
+    let mut harness = Harness::new();
+    let mut workspace = CollapsibleWorkspace::new(Id::new("workspace"), DockState::new(vec![]))
+        .with_panel(panel(PanelSide::Left, "explorer"))
+        .with_panel(keyed_panel(PanelSide::Left, "outline"))
+        .with_panel(keyed_panel(PanelSide::Left, "timeline"));
+    harness.settle(&mut workspace);
+
+    workspace.panel_by_id_mut("timeline").unwrap().toggle();
+    let events = harness.settle(&mut workspace).1;
+    assert!(
+        events.contains(&(
+            PanelSide::Left,
+            Some("timeline".to_owned()),
+            PanelEvent::Collapsed
+        )),
+        "{events:?}"
+    );
+    assert!(
+        events
+            .iter()
+            .all(|(_, id, _)| id.as_deref() == Some("timeline")),
+        "{events:?}"
+    );
+
+    workspace.toggle(PanelSide::Left);
+    let events = harness.settle(&mut workspace).1;
+    assert!(
+        events.contains(&(PanelSide::Left, None, PanelEvent::Collapsed)),
+        "{events:?}"
+    );
+}
+
+/// Two standalone panels shown in the same frame.
+struct TwoPanels(TestPanel, TestPanel);
+
+impl Scene for TwoPanels {
+    type Event = PanelEvent;
+
+    fn show(&mut self, ctx: &egui::Context) -> (Option<egui::Rect>, Vec<PanelEvent>) {
+        let mut viewer = TestViewer::default();
+        let mut events = self.0.show_with_events(ctx, &mut viewer).1;
+        events.extend(self.1.show_with_events(ctx, &mut viewer).1);
+        egui::CentralPanel::default().show(ctx, |_ui| {});
+        (None, events)
+    }
+}
+
+#[test]
+fn keyed_panels_sharing_an_id_keep_separate_egui_state() {
+    // Both panels are created with `Id::new("shared")`.
+    let keyed =
+        |name: &str| panel_with_tabs(PanelSide::Left, "shared", &[name]).with_panel_id(name);
+    let mut harness = Harness::new();
+
+    let mut scene = TwoPanels(keyed("outline"), keyed("timeline"));
+    scene.0.set_size(400.0);
+    scene.1.set_size(200.0);
+    scene.1.set_collapsed(true);
+    harness.settle(&mut scene);
+
+    // Fresh panels built from the same ids load their own saved state.
+    let mut rebuilt = TwoPanels(keyed("outline"), keyed("timeline"));
+    harness.settle(&mut rebuilt);
+    assert_eq!(rebuilt.0.get_size(), 400.0);
+    assert!(!rebuilt.0.is_collapsed());
+    assert_eq!(rebuilt.1.get_size(), 200.0);
+    assert!(rebuilt.1.is_collapsed());
+}
+
+#[test]
+fn lowest_priority_panels_collapse_first_when_space_runs_out() {
+    let mut harness = Harness::new();
+    let sized = |side, name: &str, priority| {
+        let mut panel = panel(side, name).with_collapse_priority(priority);
+        panel.set_size(300.0);
+        panel
+    };
+    let mut workspace = CollapsibleWorkspace::new(Id::new("workspace"), DockState::new(vec![]))
+        .with_panel(sized(PanelSide::Left, "explorer", 2))
+        .with_panel(sized(PanelSide::Right, "outline", 1))
+        .with_min_central_size(Vec2::new(400.0, 0.0));
+    let collapsed = |workspace: &TestWorkspace| {
+        [PanelSide::Left, PanelSide::Right]
+            .map(|side| workspace.panel(side).unwrap().is_collapsed())
+    };
+
+    harness.screen_size = Vec2::new(1280.0, 800.0);
+    harness.frame(&mut workspace, Vec::new());
+    assert_eq!(collapsed(&workspace), [false, false]);
+
+    harness.screen_size = Vec2::new(900.0, 800.0);
+    harness.frame(&mut workspace, Vec::new());
+    assert_eq!(collapsed(&workspace), [false, true]);
+
+    harness.screen_size = Vec2::new(600.0, 800.0);
+    harness.frame(&mut workspace, Vec::new());
+    assert_eq!(collapsed(&workspace), [true, true]);
+
+    harness.screen_size = Vec2::new(1280.0, 800.0);
+    harness.frame(&mut workspace, Vec::new());
+    assert_eq!(collapsed(&workspace), [false, false]);
+}
+
+#[test]
+fn collapsed_strips_count_against_the_central_space() {
+    let mut harness = Harness::new();
+    let mut explorer = panel(PanelSide::Left, "explorer");
+    explorer.set_collapsed(true);
+    let mut outline = panel(PanelSide::Right, "outline");
+    outline.set_size(300.0);
+    let mut workspace = CollapsibleWorkspace::new(Id::new("workspace"), DockState::new(vec![]))
+        .with_panel(explorer)
+        .with_panel(outline)
+        .with_min_central_size(Vec2::new(400.0, 0.0));
+    let outline_collapsed =
+        |workspace: &TestWorkspace| workspace.panel(PanelSide::Right).unwrap().is_collapsed();
+
+    // 26px strip + 300px panel + 400px central space
+    harness.screen_size = Vec2::new(726.0, 800.0);
+    harness.frame(&mut workspace, Vec::new());
+    assert!(!outline_collapsed(&workspace));
+
+    harness.screen_size = Vec2::new(725.0, 800.0);
+    harness.frame(&mut workspace, Vec::new());
+    assert!(outline_collapsed(&workspace));
+}
+
+#[test]
+fn workspace_checks_the_most_specific_shortcut_first() {
+    let mut harness = Harness::new();
+    let toggle = |shortcut: &str| {
+        Keymap::new().with_binding(shortcut.parse().unwrap(), KeyAction::TogglePanel)
+    };
+    // Left renders first; its Ctrl+B must not swallow Right's Ctrl+Alt+B.
+    let mut workspace = CollapsibleWorkspace::new(Id::new("workspace"), DockState::new(vec![]))
+        .with_panel(panel(PanelSide::Left, "explorer").with_keymap(toggle("Ctrl+B")))
+        .with_panel(panel(PanelSide::Right, "outline").with_keymap(toggle("Ctrl+Alt+B")));
+    let collapsed = |workspace: &TestWorkspace| {
+        [PanelSide::Left, PanelSide::Right]
+            .map(|side| workspace.panel(side).unwrap().is_collapsed())
+    };
+    harness.settle(&mut workspace);
+
+    let events = harness.press(&mut workspace, Modifiers::CTRL | Modifiers::ALT, Key::B);
+    assert_eq!(collapsed(&workspace), [false, true], "{events:?}");
+
+    harness.press(&mut workspace, Modifiers::CTRL, Key::B);
+    assert_eq!(collapsed(&workspace), [true, true]);
+}
+
+#[test]
+fn default_keymaps_toggle_only_their_own_panel() {
+    let mut harness = Harness::new();
+    let mut workspace = CollapsibleWorkspace::new(Id::new("workspace"), DockState::new(vec![]));
+    for side in ALL_SIDES {
+        workspace.set_panel(
+            panel(side, &format!("{side:?}")).with_keymap(Keymap::default_for_side(side)),
+        );
+    }
+    assert!(workspace.keymap_conflicts().is_empty());
+    harness.settle(&mut workspace);
+
+    for side in ALL_SIDES {
+        let keymap = Keymap::default_for_side(side);
+        let toggle = keymap.bindings[0].shortcut;
+        harness.press(&mut workspace, toggle.modifiers, toggle.key);
+
+        for other in ALL_SIDES {
+            let panel = workspace.panel(other).unwrap();
+            assert_eq!(panel.is_collapsed(), other == side, "{toggle} on {other:?}");
+        }
+        harness.press(&mut workspace, toggle.modifiers, toggle.key);
+    }
+}
+
+/// Workspace with one expanded panel per side, each holding a tab named after
+/// its side, laid out with `corners`.
+fn corner_scene(corners: CornerOwnership) -> WorkspaceScene {
+    let mut harness = Harness::new();
+    let mut scene = WorkspaceScene::new(
+        CollapsibleWorkspace::new(
+            Id::new(("corners", corners as u8)),
+            DockState::new(vec!["center".to_owned()]),
+        )
+        .with_panel(panel(PanelSide::Left, "left"))
+        .with_panel(panel(PanelSide::Right, "right"))
+        .with_panel(panel(PanelSide::Top, "top"))
+        .with_panel(panel(PanelSide::Bottom, "bottom"))
+        .with_corners(corners),
+    );
+    harness.settle(&mut scene);
+    scene
+}
+
+#[test]
+fn side_panels_own_the_corners() {
+    let scene = corner_scene(CornerOwnership::SidePanels);
+    let [left, right, top, bottom, center] =
+        ["left", "right", "top", "bottom", "center"].map(|tab| scene.content_rect(tab));
+
+    for rect in [left, right] {
+        assert!(rect.top() < 40.0, "{rect:?}");
+        assert!(rect.bottom() > SCREEN_SIZE.y - 1.0, "{rect:?}");
+    }
+    for rect in [top, bottom, center] {
+        assert!(
+            rect.left() >= left.right(),
+            "{rect:?} starts after {left:?}"
+        );
+        assert!(
+            rect.right() <= right.left(),
+            "{rect:?} ends before {right:?}"
+        );
+    }
+}
+
+#[test]
+fn top_and_bottom_panels_own_the_corners() {
+    let scene = corner_scene(CornerOwnership::TopBottomPanels);
+    let [left, right, top, bottom, center] =
+        ["left", "right", "top", "bottom", "center"].map(|tab| scene.content_rect(tab));
+
+    for rect in [top, bottom] {
+        assert!(rect.left() < 1.0, "{rect:?}");
+        assert!(rect.right() > SCREEN_SIZE.x - 1.0, "{rect:?}");
+    }
+    for rect in [left, right, center] {
+        assert!(rect.top() >= top.bottom(), "{rect:?} starts below {top:?}");
+        // Content starts below the tab bar, so compare with the bottom panel's
+        // content top rather than its edge.
+        assert!(
+            rect.bottom() <= bottom.top(),
+            "{rect:?} ends above {bottom:?}"
+        );
+    }
+}